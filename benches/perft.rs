@@ -0,0 +1,33 @@
+use std::time::{Duration, Instant};
+
+use chess_voting::game::{
+    fen::{game_from_fen, STARTING_FEN},
+    perft::perft,
+};
+
+const KIWIPETE_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const ITALIAN_GAME_FEN: &str =
+    "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+
+fn bench(name: &str, fen: &str, depth: u32) {
+    let mut game = game_from_fen(fen).unwrap();
+    let mut runs = 0;
+    let mut nodes = 0;
+    let start = Instant::now();
+    while runs == 0 || start.elapsed() < Duration::from_secs(2) {
+        nodes = perft(&mut game, depth);
+        runs += 1;
+    }
+    let per_run = start.elapsed() / runs;
+    println!(
+        "{name:<24} depth {depth}: {nodes:>8} nodes in {per_run:>10.2?} ({:.0} nodes/s)",
+        nodes as f64 / per_run.as_secs_f64()
+    );
+}
+
+fn main() {
+    bench("initial position", STARTING_FEN, 3);
+    bench("initial position", STARTING_FEN, 4);
+    bench("italian game", ITALIAN_GAME_FEN, 3);
+    bench("kiwipete", KIWIPETE_FEN, 3);
+}
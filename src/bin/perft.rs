@@ -0,0 +1,50 @@
+use std::{env, process, time::Instant};
+
+use chess_voting::game::{
+    fen::{game_from_fen, STARTING_FEN},
+    perft::perft_divide,
+};
+
+// Usage: perft <depth> [fen]
+// Prints the node count below every legal move and the total, in the same format as most
+// engines, so the output can be diffed against them.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let depth = match args.first().map(|depth| depth.parse::<u32>()) {
+        Some(Ok(depth)) if depth > 0 => depth,
+        _ => {
+            eprintln!("Usage: perft <depth> [fen]");
+            process::exit(1);
+        }
+    };
+    let fen = match args.len() {
+        1 => STARTING_FEN.to_string(),
+        _ => args[1..].join(" "),
+    };
+
+    let mut game = match game_from_fen(&fen) {
+        Ok(game) => game,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let start = Instant::now();
+    let mut divided = perft_divide(&mut game, depth);
+    let elapsed = start.elapsed();
+    divided.sort();
+
+    let mut total = 0;
+    for (legal_move, nodes) in divided {
+        println!("{}: {}", legal_move, nodes);
+        total += nodes;
+    }
+    println!();
+    println!("Nodes searched: {}", total);
+    println!(
+        "Time: {:.2?} ({:.0} nodes/s)",
+        elapsed,
+        total as f64 / elapsed.as_secs_f64()
+    );
+}
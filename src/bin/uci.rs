@@ -0,0 +1,351 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+use chess_voting::{
+    engine::{search_until_stopped, SearchLimits, MATE_SCORE, MAX_MATE_PLY},
+    game::{
+        chess_move::Move,
+        chess_piece::Color,
+        fen::{game_from_fen, get_fen, STARTING_FEN},
+        perft::perft_divide,
+        Game,
+    },
+};
+
+// searches without a depth only end on their node or time limit or on stop
+const MAX_DEPTH: u32 = 64;
+// how many moves the time left has to last if the GUI doesn't send movestogo
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+
+static STOP: AtomicBool = AtomicBool::new(false);
+
+// Speaks UCI on stdin and stdout, so GUIs and test harnesses can play against the built-in
+// engine and cross-check the move generator. Besides the standard commands it understands
+// `go perft <depth>` and `d`, which prints the current position as FEN.
+fn main() {
+    // stdin is read on its own thread, so that stop reaches a search that is still running
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            match line.split_whitespace().next() {
+                Some("go") => STOP.store(false, Ordering::Relaxed),
+                // a ponder search simply ends on ponderhit instead of going on as a normal one
+                Some("stop") | Some("ponderhit") | Some("quit") => {
+                    STOP.store(true, Ordering::Relaxed)
+                }
+                _ => (),
+            }
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut game = Game::new();
+    let mut stdout = io::stdout();
+    for line in receiver {
+        if !handle_command(&line, &mut game, &STOP, &mut stdout) {
+            break;
+        }
+        stdout.flush().unwrap();
+    }
+}
+
+// returns false once the engine should quit
+fn handle_command(line: &str, game: &mut Game, stop: &AtomicBool, out: &mut impl Write) -> bool {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    match tokens.first().copied() {
+        Some("uci") => {
+            writeln!(out, "id name chess-voting").unwrap();
+            writeln!(out, "id author QuodEstDubitandum").unwrap();
+            writeln!(out, "uciok").unwrap();
+        }
+        Some("isready") => writeln!(out, "readyok").unwrap(),
+        Some("ucinewgame") => *game = Game::new(),
+        Some("position") => {
+            if let Err(e) = set_position(&tokens[1..], game) {
+                writeln!(out, "info string {}", e).unwrap();
+            }
+        }
+        Some("go") => go(&tokens[1..], game, stop, out),
+        Some("d") => writeln!(out, "{}", get_fen(game)).unwrap(),
+        Some("quit") => return false,
+        // stop has already been passed on to the search by the thread reading stdin, and unknown
+        // commands are ignored as the protocol asks for
+        _ => (),
+    }
+
+    true
+}
+
+// position [startpos | fen <fen>] [moves <move>...]
+fn set_position(tokens: &[&str], game: &mut Game) -> Result<(), String> {
+    let moves_index = tokens
+        .iter()
+        .position(|&token| token == "moves")
+        .unwrap_or(tokens.len());
+    let fen = match tokens.first() {
+        Some(&"startpos") => STARTING_FEN.to_string(),
+        Some(&"fen") => tokens[1..moves_index].join(" "),
+        _ => return Err("Expected startpos or fen".to_string()),
+    };
+
+    let mut new_game = game_from_fen(&fen)?;
+    for &uci in tokens.iter().skip(moves_index + 1) {
+        let chess_move = Move::from_uci(uci).map_err(|e| format!("{}: {}", uci, e))?;
+        new_game
+            .validate_and_make_move(chess_move)
+            .map_err(|e| format!("{}: {}", uci, e))?;
+    }
+    *game = new_game;
+
+    Ok(())
+}
+
+// go [depth <depth>] [nodes <nodes>] [movetime <ms>] [wtime <ms>] [btime <ms>] [winc <ms>]
+// [binc <ms>] [movestogo <moves>] [infinite] | go perft <depth>
+fn go(tokens: &[&str], game: &mut Game, stop: &AtomicBool, out: &mut impl Write) {
+    let get_value = |name: &str| {
+        tokens
+            .iter()
+            .position(|&token| token == name)
+            .and_then(|index| tokens.get(index + 1))
+            .and_then(|value| value.parse::<u64>().ok())
+    };
+
+    if let Some(depth) = get_value("perft") {
+        // the position itself is the only one at depth 0
+        if depth == 0 {
+            writeln!(out).unwrap();
+            writeln!(out, "Nodes searched: 1").unwrap();
+            return;
+        }
+        let mut divided = perft_divide(game, depth as u32);
+        divided.sort();
+        let total: u64 = divided.iter().map(|(_, nodes)| nodes).sum();
+        for (legal_move, nodes) in divided {
+            writeln!(out, "{}: {}", legal_move, nodes).unwrap();
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "Nodes searched: {}", total).unwrap();
+        return;
+    }
+
+    for token in ["searchmoves", "mate"] {
+        if tokens.contains(&token) {
+            writeln!(out, "info string go {} is not supported", token).unwrap();
+        }
+    }
+
+    // without a limit, like with infinite, the search runs until stop
+    let (time_left, increment) = match game.next_to_move {
+        Color::WHITE => (get_value("wtime"), get_value("winc")),
+        Color::BLACK => (get_value("btime"), get_value("binc")),
+    };
+    let time = match (get_value("movetime"), time_left) {
+        (Some(move_time), _) => Some(Duration::from_millis(move_time)),
+        (None, Some(time_left)) => Some(get_move_time(
+            time_left,
+            increment.unwrap_or(0),
+            get_value("movestogo"),
+        )),
+        (None, None) => None,
+    };
+    let limits = SearchLimits {
+        depth: get_value("depth").map_or(MAX_DEPTH, |depth| depth as u32),
+        nodes: get_value("nodes"),
+        time,
+        randomness: 0,
+    };
+    let result = search_until_stopped(game, limits, stop);
+    // the GUI expects the move only after it sent stop, even if the search ended before
+    if tokens.contains(&"infinite") || tokens.contains(&"ponder") {
+        while !stop.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    // mates are given in moves instead of centipawns, negative if the engine gets mated
+    let score = match result.score {
+        score if score >= MATE_SCORE - MAX_MATE_PLY => {
+            format!("mate {}", (MATE_SCORE - score + 1) / 2)
+        }
+        // mate 0 if the engine is already mated
+        score if score <= -MATE_SCORE + MAX_MATE_PLY => {
+            format!("mate {}", -((MATE_SCORE + score) / 2))
+        }
+        score => format!("cp {}", score),
+    };
+    writeln!(
+        out,
+        "info depth {} score {} nodes {}",
+        result.depth, score, result.nodes
+    )
+    .unwrap();
+    match result.best_move {
+        Some(best_move) => writeln!(out, "bestmove {}", best_move).unwrap(),
+        None => writeln!(out, "bestmove (none)").unwrap(),
+    }
+}
+
+// an even share of the time left for the moves until the next time control plus the increment,
+// but never more than half of the time left
+fn get_move_time(time_left: u64, increment: u64, moves_to_go: Option<u64>) -> Duration {
+    let moves_to_go = moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+    Duration::from_millis((time_left / moves_to_go + increment).min(time_left / 2))
+}
+
+#[cfg(test)]
+mod test_uci {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use chess_voting::game::{fen::get_fen, Game};
+
+    use crate::handle_command;
+
+    fn run(commands: &[&str]) -> (Game, String) {
+        run_with_stop(commands, false)
+    }
+
+    fn run_with_stop(commands: &[&str], stop: bool) -> (Game, String) {
+        let mut game = Game::new();
+        let stop = AtomicBool::new(stop);
+        let mut out = vec![];
+        for command in commands {
+            handle_command(command, &mut game, &stop, &mut out);
+        }
+
+        (game, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_handshake() {
+        let (_, out) = run(&["uci", "isready"]);
+        assert!(out.contains("uciok\n"));
+        assert!(out.ends_with("readyok\n"));
+    }
+
+    #[test]
+    fn test_position_with_moves() {
+        let (game, _) = run(&["position startpos moves e2e4 c7c5 g1f3"]);
+        assert_eq!(
+            get_fen(&game),
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 0 2"
+        );
+
+        let (game, _) = run(&["position fen 7k/P7/8/8/8/8/8/K7 w - - 0 1 moves a7a8q"]);
+        assert_eq!(get_fen(&game), "Q6k/8/8/8/8/8/8/K7 b - - 0 1");
+    }
+
+    #[test]
+    fn test_invalid_position_keeps_game() {
+        let (game, out) = run(&[
+            "position startpos moves e2e4",
+            "position startpos moves e2e5",
+        ]);
+        assert!(out.starts_with("info string e2e5"));
+        assert_eq!(
+            get_fen(&game),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+    }
+
+    #[test]
+    fn test_go() {
+        let (_, out) = run(&[
+            "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+            "go depth 3",
+        ]);
+        assert!(out.contains("score mate 1"));
+        assert!(out.ends_with("bestmove a1a8\n"));
+
+        let (_, out) = run(&[
+            "position fen R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1",
+            "go depth 3",
+        ]);
+        assert!(out.ends_with("bestmove (none)\n"));
+    }
+
+    #[test]
+    fn test_go_perft() {
+        let (_, out) = run(&["position startpos", "go perft 2"]);
+        assert!(out.contains("e2e4: 20\n"));
+        assert!(out.ends_with("Nodes searched: 400\n"));
+
+        let (_, out) = run(&["position startpos", "go perft 0"]);
+        assert_eq!(out, "\nNodes searched: 1\n");
+    }
+
+    #[test]
+    fn test_go_with_time() {
+        let (_, out) = run(&["position startpos", "go movetime 50"]);
+        assert!(out.contains("\nbestmove "));
+
+        let (_, out) = run(&[
+            "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+            "go wtime 1000 btime 1000 winc 10 binc 10",
+        ]);
+        assert!(out.ends_with("bestmove a1a8\n"));
+    }
+
+    #[test]
+    fn test_go_infinite_until_stop() {
+        let (_, out) = run_with_stop(&["position startpos", "go infinite"], true);
+        assert!(out.contains("bestmove "));
+        assert!(!out.contains("bestmove (none)"));
+    }
+
+    #[test]
+    fn test_go_infinite_waits_for_stop() {
+        let stop = AtomicBool::new(false);
+        let mut game = Game::new();
+        let mut out = vec![];
+        let started = Instant::now();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(200));
+                stop.store(true, Ordering::Relaxed);
+            });
+            // the mate is found right away, but the move is only sent after stop
+            handle_command(
+                "position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+                &mut game,
+                &stop,
+                &mut out,
+            );
+            handle_command("go infinite", &mut game, &stop, &mut out);
+        });
+
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert!(String::from_utf8(out).unwrap().ends_with("bestmove a1a8\n"));
+    }
+
+    #[test]
+    fn test_go_when_mated() {
+        let (_, out) = run(&[
+            "position fen R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1",
+            "go depth 3",
+        ]);
+        assert!(out.contains("score mate 0 "));
+    }
+
+    #[test]
+    fn test_unsupported_go() {
+        let (_, out) = run(&["position startpos", "go depth 1 searchmoves e2e4"]);
+        assert!(out.starts_with("info string go searchmoves is not supported\n"));
+    }
+}
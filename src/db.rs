@@ -1,25 +1,119 @@
 use libsql::{de, params, Builder, Connection, Rows};
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    engine::EngineOpponent,
+    game::{
+        chess_move,
+        chess_piece::{Color, Piece},
+        fen::get_fen,
+        move_gen::find_move_by_notation,
+        Game,
+    },
+    time_control::{Clock, TimeControl},
+    utils::{
+        convert_notation::{
+            get_notation_from_square, get_piece_char, get_promotion_char, get_promotion_piece,
+            get_square_from_notation,
+        },
+        error::INVALID_HISTORY_ERROR,
+    },
+    voting::{Teams, VotingMethod},
+};
+
 pub async fn connect_db() -> Connection {
     let db = Builder::new_local("local.db")
         .build()
         .await
         .expect("Could not connect local database");
-    let conn = db.connect().unwrap();
-
-    conn
+    db.connect().unwrap()
 }
 
+// columns that were added to the tables after the schema got versioned, as table and column
+// definition in the order they were added. A database stores how many of them it has in its
+// user_version, offset by one as 0 is the version of a database that is not versioned yet
+const MIGRATIONS: &[(&str, &str)] = &[
+    // the squares and FEN of the moves from before are filled in by backfill_moves
+    ("Move", "from_square VARCHAR(2) NOT NULL DEFAULT ''"),
+    ("Move", "to_square VARCHAR(2) NOT NULL DEFAULT ''"),
+    ("Move", "promotion VARCHAR(1)"),
+    ("Move", "captured_piece VARCHAR(1)"),
+    ("Move", "fen VARCHAR(100) NOT NULL DEFAULT ''"),
+    ("Move", "votes INTEGER"),
+    // a column can't be added with the current time as default
+    ("Move", "played_at TEXT NOT NULL DEFAULT ''"),
+    ("Move", "engine_move VARCHAR(5)"),
+    (
+        "Game",
+        "voting_close_time VARCHAR(5) NOT NULL DEFAULT '00:00'",
+    ),
+    ("Game", "voting_closes_at TEXT"),
+    (
+        "Game",
+        "voting_method VARCHAR(20) NOT NULL DEFAULT 'PLURALITY'",
+    ),
+    ("VoteLog", "ballot VARCHAR(255)"),
+    ("Game", "team_white VARCHAR(50)"),
+    ("Game", "team_black VARCHAR(50)"),
+    ("Game", "time_per_move INTEGER"),
+    ("Game", "time_total INTEGER"),
+    ("Game", "time_increment INTEGER"),
+    ("Game", "white_time_left INTEGER"),
+    ("Game", "black_time_left INTEGER"),
+    ("Game", "turn_started_at TEXT"),
+    // the games from before all started from the standard position
+    ("Game", "variant VARCHAR(20) NOT NULL DEFAULT 'STANDARD'"),
+    (
+        "Game",
+        "starting_fen VARCHAR(100) NOT NULL DEFAULT 'rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1'",
+    ),
+];
+
+// only creates the tables that are missing and adds the missing columns to the others, so the
+// archived games, the vote log and the team memberships survive a restart
 pub async fn seed_db(db: &Connection) {
-    db.execute_batch(
-        r#"
+    let version = get_schema_version(db).await;
+    if version == 0 {
+        // before the versioning the tables were recreated on every start, so they hold nothing
+        // that is worth migrating
+        db.execute_batch(
+            r#"
+    DROP TABLE IF EXISTS PlayedVote;
     DROP TABLE IF EXISTS Move;
     DROP TABLE IF EXISTS Game;
+    "#,
+        )
+        .await
+        .expect("Cant drop unversioned tables");
+    }
 
+    db.execute_batch(
+        r#"
     CREATE TABLE IF NOT EXISTS Game(
     game_id VARCHAR(255) PRIMARY KEY,
-    result VARCHAR(10)
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    finished_at TEXT,
+    result VARCHAR(10),
+    termination VARCHAR(20),
+    voting_method VARCHAR(20),
+    voting_close_time VARCHAR(5),
+    voting_closes_at TEXT,
+    mode VARCHAR(20),
+    variant VARCHAR(20),
+    starting_fen VARCHAR(100),
+    team_white VARCHAR(50),
+    team_black VARCHAR(50),
+    time_per_move INTEGER,
+    time_total INTEGER,
+    time_increment INTEGER,
+    white_time_left INTEGER,
+    black_time_left INTEGER,
+    turn_started_at TEXT,
+    engine_color VARCHAR(10),
+    engine_strength VARCHAR(10),
+    engine_depth INTEGER,
+    engine_nodes INTEGER,
+    engine_randomness INTEGER
     );
 
     CREATE TABLE IF NOT EXISTS Move(
@@ -28,6 +122,14 @@ pub async fn seed_db(db: &Connection) {
     turn INTEGER,
     player VARCHAR(10),
     move_notation VARCHAR(10),
+    from_square VARCHAR(2),
+    to_square VARCHAR(2),
+    promotion VARCHAR(1),
+    captured_piece VARCHAR(1),
+    fen VARCHAR(100),
+    votes INTEGER,
+    engine_move VARCHAR(5),
+    played_at TEXT DEFAULT CURRENT_TIMESTAMP,
     FOREIGN KEY(game_id) REFERENCES Game(game_id)
     );
 
@@ -36,10 +138,161 @@ pub async fn seed_db(db: &Connection) {
     move_notation VARCHAR(10) UNIQUE,
     votes INTEGER
     );
+
+    CREATE TABLE IF NOT EXISTS PlayedVote(
+    played_vote_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    move_id INTEGER,
+    move_notation VARCHAR(10),
+    votes INTEGER,
+    FOREIGN KEY(move_id) REFERENCES Move(move_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS Ballot(
+    ballot_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    moves VARCHAR(255)
+    );
+
+    CREATE TABLE IF NOT EXISTS PlayedBallot(
+    played_ballot_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    move_id INTEGER,
+    moves VARCHAR(255),
+    FOREIGN KEY(move_id) REFERENCES Move(move_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS Voter(
+    voter_hash VARCHAR(64) PRIMARY KEY
+    );
+
+    CREATE TABLE IF NOT EXISTS VoteLog(
+    vote_log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    voter_hash VARCHAR(64),
+    game_id VARCHAR(255),
+    turn INTEGER,
+    player VARCHAR(10),
+    move_notation VARCHAR(10),
+    ballot VARCHAR(255),
+    voted_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(game_id) REFERENCES Game(game_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS TeamMember(
+    team_member_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    voter_hash VARCHAR(64),
+    team VARCHAR(50),
+    joined_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(voter_hash, team)
+    );
     "#,
     )
     .await
     .expect("Cant seed DB");
+
+    migrate_db(db, version).await;
+    backfill_moves(db).await;
+}
+
+// the moves that were stored with their notation only get their squares and FEN by replaying
+// their games. A game that can't be replayed keeps the rest of its moves as they are
+async fn backfill_moves(db: &Connection) {
+    let mut rows: Rows = db
+        .query(
+            "SELECT move_id, game_id, move_notation FROM Move
+             WHERE game_id IN (SELECT game_id FROM Move WHERE from_square = '')
+             ORDER BY game_id, turn, move_id",
+            (),
+        )
+        .await
+        .expect("Could not get moves to backfill");
+
+    let mut backfilled_moves = vec![];
+    let mut game_id = String::new();
+    let mut game = Game::new();
+    let mut replayable = true;
+    while let Some(row) = rows.next().await.unwrap() {
+        let move_id: i64 = row.get(0).unwrap();
+        let move_game_id: String = row.get(1).unwrap();
+        let move_notation: String = row.get(2).unwrap();
+        if move_game_id != game_id {
+            game_id = move_game_id;
+            game = Game::new();
+            replayable = true;
+        }
+        if !replayable {
+            continue;
+        }
+        let Some(played_move) = find_move_by_notation(&mut game, &move_notation) else {
+            replayable = false;
+            continue;
+        };
+        let captured_piece = game.get_captured_piece(played_move);
+        game.make_move(played_move);
+        let (from_square, to_square, promotion, captured_piece) =
+            get_move_columns(played_move, captured_piece);
+        backfilled_moves.push((
+            move_id,
+            from_square,
+            to_square,
+            promotion,
+            captured_piece,
+            get_fen(&game),
+        ));
+    }
+
+    for (move_id, from_square, to_square, promotion, captured_piece, fen) in backfilled_moves {
+        db.execute(
+            "UPDATE Move SET from_square = ?1, to_square = ?2, promotion = ?3,
+             captured_piece = ?4, fen = ?5 WHERE move_id = ?6",
+            params![
+                from_square,
+                to_square,
+                promotion,
+                captured_piece,
+                fen,
+                move_id
+            ],
+        )
+        .await
+        .expect("Could not backfill move");
+    }
+}
+
+async fn get_schema_version(db: &Connection) -> u32 {
+    let mut rows: Rows = db
+        .query("PRAGMA user_version", ())
+        .await
+        .expect("Could not get schema version");
+
+    rows.next()
+        .await
+        .unwrap()
+        .map_or(0, |row| row.get(0).unwrap())
+}
+
+async fn migrate_db(db: &Connection, version: u32) {
+    let applied = (version.max(1) as usize - 1).min(MIGRATIONS.len());
+    for (table, column) in &MIGRATIONS[applied..] {
+        // the tables that were just created already have the column
+        let name = column.split_whitespace().next().unwrap();
+        let mut rows: Rows = db
+            .query(
+                "SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2",
+                params![*table, name],
+            )
+            .await
+            .expect("Could not get columns");
+        if rows.next().await.unwrap().is_some() {
+            continue;
+        }
+        db.execute(&format!("ALTER TABLE {} ADD COLUMN {}", table, column), ())
+            .await
+            .expect("Could not migrate DB");
+    }
+    db.execute(
+        &format!("PRAGMA user_version = {}", MIGRATIONS.len() + 1),
+        (),
+    )
+    .await
+    .expect("Could not set schema version");
 }
 
 pub struct DB {
@@ -51,6 +304,106 @@ pub struct Move {
     pub move_notation: String,
     pub turn: u32,
     pub player: String,
+    // squares like e2, pieces as their letter like Q
+    pub from_square: String,
+    pub to_square: String,
+    pub promotion: Option<String>,
+    pub captured_piece: Option<String>,
+    // the position after the move
+    pub fen: String,
+    // the votes the move won with, None if nobody voted for it, e.g. for the engine's moves
+    pub votes: Option<u32>,
+    // the engine's best move in UCI notation when the community played the move
+    pub engine_move: Option<String>,
+    pub played_at: String,
+}
+
+impl Move {
+    pub fn get_chess_move(&self) -> Result<chess_move::Move, &'static str> {
+        let promotion = match &self.promotion {
+            Some(promotion) => Some(
+                promotion
+                    .chars()
+                    .next()
+                    .and_then(get_promotion_piece)
+                    .ok_or(INVALID_HISTORY_ERROR)?,
+            ),
+            None => None,
+        };
+
+        Ok(chess_move::Move::new(
+            get_square_from_notation(&self.from_square)?,
+            get_square_from_notation(&self.to_square)?,
+            promotion,
+        ))
+    }
+}
+
+// the squares, promotion and captured piece a move is stored with, which `Move::get_chess_move`
+// turns back into the move
+fn get_move_columns(
+    played_move: chess_move::Move,
+    captured_piece: Option<Piece>,
+) -> (String, String, Option<String>, Option<String>) {
+    (
+        get_notation_from_square(played_move.from),
+        get_notation_from_square(played_move.to),
+        played_move
+            .promotion
+            .and_then(get_promotion_char)
+            .map(String::from),
+        captured_piece.map(|piece| get_piece_char(piece).to_string()),
+    )
+}
+
+// the engine columns are only set against the engine and the team columns only when two teams
+// play each other
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GameRecord {
+    pub game_id: String,
+    // UTC timestamps like 2024-05-01 00:00:00
+    pub created_at: String,
+    pub finished_at: Option<String>,
+    pub result: Option<String>,
+    // how the game ended, CHECKMATE, VARIANT_WIN, STALEMATE, RESIGNATION, AGREEMENT, TIMEOUT or
+    // MANUAL if it was finished through /game/finish
+    pub termination: Option<String>,
+    pub voting_method: String,
+    // the UTC time of day like 00:00 at which the voting for a move closes, and when the voting
+    // for the current move closes
+    pub voting_close_time: String,
+    pub voting_closes_at: Option<String>,
+    pub mode: String,
+    // e.g. STANDARD or CHESS960, and the position the game started from to replay its moves
+    pub variant: String,
+    pub starting_fen: String,
+    pub team_white: Option<String>,
+    pub team_black: Option<String>,
+    // the time control in seconds, the time left of both sides at the start of the current move
+    // and when it started. All null without a time control
+    pub time_per_move: Option<u64>,
+    pub time_total: Option<u64>,
+    pub time_increment: Option<u64>,
+    pub white_time_left: Option<u64>,
+    pub black_time_left: Option<u64>,
+    pub turn_started_at: Option<String>,
+    pub engine_color: Option<String>,
+    pub engine_strength: Option<String>,
+    pub engine_depth: Option<u32>,
+    pub engine_nodes: Option<u64>,
+    pub engine_randomness: Option<i32>,
+}
+
+// a game in the archive list, without its moves
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GameSummary {
+    pub game_id: String,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+    pub result: Option<String>,
+    pub termination: Option<String>,
+    pub mode: String,
+    pub move_count: u32,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -59,44 +412,414 @@ pub struct Vote {
     pub votes: u32,
 }
 
+// all votes of the vote log that were cast for one move of a game
+#[derive(Serialize, Debug)]
+pub struct TurnVotes {
+    pub turn: u32,
+    pub player: String,
+    pub votes: Vec<Vote>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LoggedVotes {
+    turn: u32,
+    player: String,
+    move_notation: String,
+    votes: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Turnout {
+    // the day or hour, e.g. 2024-05-01 or 2024-05-01 13:00
+    pub period: String,
+    pub votes: u32,
+    pub voters: u32,
+}
+
+#[derive(Deserialize, Debug)]
+struct StoredClock {
+    time_per_move: Option<u64>,
+    time_total: Option<u64>,
+    time_increment: Option<u64>,
+    white_time_left: Option<u64>,
+    black_time_left: Option<u64>,
+    elapsed: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EngineAgreement {
+    // moves of the community for which the engine's best move is known
+    pub community_moves: u32,
+    pub engine_moves: u32,
+}
+
 impl DB {
     pub async fn new() -> DB {
         let conn = connect_db().await;
         seed_db(&conn).await;
-        DB { conn }
+        let db = DB { conn };
+        // a new game is started on every start, so the open votes of the game before don't count
+        db.clear_votes().await;
+        db
+    }
+    pub async fn create_game(
+        &self,
+        game: &Game,
+        opponent: Option<&EngineOpponent>,
+        teams: Option<&Teams>,
+        time_control: Option<&TimeControl>,
+        voting_method: VotingMethod,
+        voting_close_time: &str,
+    ) {
+        let id = game.id.to_string();
+        let id = id.as_str();
+        let result = match (opponent, teams) {
+            (None, None) => {
+                self.conn
+                    .execute(
+                        "INSERT INTO Game(game_id, result, mode, voting_method, voting_close_time)
+                         VALUES(?1, null, 'COMMUNITY', ?2, ?3)",
+                        params![id, voting_method.as_str(), voting_close_time],
+                    )
+                    .await
+            }
+            (None, Some(teams)) => {
+                self.conn
+                    .execute(
+                        "INSERT INTO Game(game_id, result, mode, team_white, team_black,
+                         voting_method, voting_close_time)
+                         VALUES(?1, null, 'TEAMS', ?2, ?3, ?4, ?5)",
+                        params![
+                            id,
+                            teams.white.as_str(),
+                            teams.black.as_str(),
+                            voting_method.as_str(),
+                            voting_close_time
+                        ],
+                    )
+                    .await
+            }
+            // the teams are checked against the opponent before, so the engine wins here
+            (Some(opponent), _) => {
+                let limits = opponent.strength.get_search_limits();
+                let color = match opponent.color {
+                    Color::WHITE => "WHITE",
+                    Color::BLACK => "BLACK",
+                };
+                self.conn
+                    .execute(
+                        "INSERT INTO Game(game_id, result, mode, engine_color, engine_strength, 
+                         engine_depth, engine_nodes, engine_randomness, voting_method,
+                         voting_close_time) 
+                         VALUES(?1, null, 'ENGINE', ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![
+                            id,
+                            color,
+                            opponent.strength.as_str(),
+                            limits.depth,
+                            limits.nodes.map(|nodes| nodes as i64),
+                            limits.randomness,
+                            voting_method.as_str(),
+                            voting_close_time
+                        ],
+                    )
+                    .await
+            }
+        };
+        result.expect("Could not create a new game");
+        self.save_start_position(game).await;
+        self.open_voting(id).await;
+        if let Some(time_control) = time_control {
+            self.start_clock(id, time_control).await;
+        }
+    }
+    async fn save_start_position(&self, game: &Game) {
+        self.conn
+            .execute(
+                "UPDATE Game SET variant = ?2, starting_fen = ?3 WHERE game_id = ?1",
+                params![game.id.to_string(), game.variant.as_str(), get_fen(game)],
+            )
+            .await
+            .expect("Could not save the start position");
     }
-    pub async fn create_game(&self, id: &str) {
+    async fn start_clock(&self, id: &str, time_control: &TimeControl) {
+        let to_seconds = |seconds: Option<u64>| seconds.map(|seconds| seconds as i64);
         self.conn
             .execute(
-                "INSERT INTO Game(game_id, result) VALUES(?1, null)",
+                "UPDATE Game SET time_per_move = ?2, time_total = ?3, time_increment = ?4,
+                 white_time_left = ?3, black_time_left = ?3, turn_started_at = CURRENT_TIMESTAMP
+                 WHERE game_id = ?1",
+                params![
+                    id,
+                    to_seconds(time_control.seconds_per_move),
+                    to_seconds(time_control.total_seconds),
+                    time_control.increment_seconds as i64
+                ],
+            )
+            .await
+            .expect("Could not start the clock");
+    }
+    // None if the game is played without a time control
+    pub async fn get_clock(&self, id: &str) -> Option<Clock> {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT time_per_move, time_total, time_increment, white_time_left,
+                 black_time_left,
+                 MAX(CAST(strftime('%s', 'now') AS INTEGER)
+                 - CAST(strftime('%s', turn_started_at) AS INTEGER), 0) AS elapsed
+                 FROM Game WHERE game_id = ?1 AND turn_started_at IS NOT NULL",
                 params![id],
             )
             .await
-            .expect("Could not create a new game");
+            .expect("Could not get the clock");
+
+        let stored_clock = de::from_row::<StoredClock>(&rows.next().await.unwrap()?).unwrap();
+        Some(Clock {
+            time_control: TimeControl {
+                seconds_per_move: stored_clock.time_per_move,
+                total_seconds: stored_clock.time_total,
+                increment_seconds: stored_clock.time_increment.unwrap_or(0),
+            },
+            white_time_left: stored_clock.white_time_left,
+            black_time_left: stored_clock.black_time_left,
+            elapsed: stored_clock.elapsed,
+        })
     }
-    pub async fn finish_game(&self, result: &str, id: &str) {
+    // stores the budgets of both sides and starts the next move
+    pub async fn update_clock(&self, id: &str, clock: &Clock) {
         self.conn
             .execute(
-                "UPDATE TABLE Game SET result = ?1 WHERE game_id = ?2",
-                params![result, id],
+                "UPDATE Game SET white_time_left = ?2, black_time_left = ?3,
+                 turn_started_at = CURRENT_TIMESTAMP WHERE game_id = ?1",
+                params![
+                    id,
+                    clock.white_time_left.map(|seconds| seconds as i64),
+                    clock.black_time_left.map(|seconds| seconds as i64)
+                ],
             )
             .await
-            .expect("Could not create a new game");
+            .expect("Could not update the clock");
     }
-    pub async fn insert_move(&self, turn: u32, id: &str, new_move: &str, player: &str) {
+    // the voting for the next move closes at the next time the close time of the game comes up
+    pub async fn open_voting(&self, id: &str) {
         self.conn
             .execute(
-                "INSERT INTO Move(turn, move_notation, player, game_id) VALUES(?1, ?2, ?3, ?4)",
-                params![turn, new_move, player, id],
+                "UPDATE Game SET voting_closes_at = CASE
+                 WHEN time('now') < voting_close_time || ':00'
+                 THEN date('now') || ' ' || voting_close_time || ':00'
+                 ELSE date('now', '+1 day') || ' ' || voting_close_time || ':00' END
+                 WHERE game_id = ?1",
+                params![id],
+            )
+            .await
+            .expect("Could not open voting");
+    }
+    pub async fn is_voting_open(&self, id: &str) -> bool {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT datetime('now') < voting_closes_at FROM Game WHERE game_id = ?1",
+                params![id],
+            )
+            .await
+            .expect("Could not check voting deadline");
+
+        rows.next()
+            .await
+            .unwrap()
+            .is_some_and(|row| row.get::<bool>(0).unwrap_or(false))
+    }
+    pub async fn finish_game(&self, result: &str, termination: &str, id: &str) {
+        self.conn
+            .execute(
+                "UPDATE Game SET result = ?1, termination = ?2, finished_at = CURRENT_TIMESTAMP
+                 WHERE game_id = ?3",
+                params![result, termination, id],
+            )
+            .await
+            .expect("Could not finish game");
+        // the open votes were for a move of the finished game
+        self.clear_votes().await;
+    }
+    // newest games first
+    pub async fn get_games(&self, limit: u32, offset: u32) -> Vec<GameSummary> {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT Game.game_id, created_at, finished_at, result, termination, mode,
+                 COUNT(Move.move_id) AS move_count
+                 FROM Game LEFT JOIN Move ON Move.game_id = Game.game_id
+                 GROUP BY Game.game_id ORDER BY Game.rowid DESC LIMIT ?1 OFFSET ?2",
+                params![limit, offset],
+            )
+            .await
+            .expect("Could not get games");
+
+        let mut games: Vec<GameSummary> = vec![];
+        while let Some(row) = rows.next().await.unwrap() {
+            games.push(de::from_row::<GameSummary>(&row).unwrap());
+        }
+
+        games
+    }
+    pub async fn count_games(&self) -> u32 {
+        let mut rows: Rows = self
+            .conn
+            .query("SELECT COUNT(*) FROM Game", ())
+            .await
+            .expect("Could not count games");
+
+        rows.next()
+            .await
+            .unwrap()
+            .map_or(0, |row| row.get(0).unwrap())
+    }
+    pub async fn get_game(&self, id: &str) -> Option<GameRecord> {
+        let mut rows: Rows = self
+            .conn
+            .query("SELECT * FROM Game WHERE game_id = ?1", params![id])
+            .await
+            .expect("Could not get game");
+
+        rows.next()
+            .await
+            .unwrap()
+            .map(|row| de::from_row::<GameRecord>(&row).unwrap())
+    }
+    // the votes of the turn are kept with the move that was played, so they can be restored if
+    // the move is taken back, and the tally starts over for the next move
+    pub async fn insert_move(
+        &self,
+        game: &Game,
+        played_move: chess_move::Move,
+        captured_piece: Option<Piece>,
+    ) -> i64 {
+        // the game is given after the move, so the player is the one that is not to move anymore
+        let player = match game.next_to_move {
+            Color::WHITE => "BLACK",
+            Color::BLACK => "WHITE",
+        };
+        let (from_square, to_square, promotion, captured_piece) =
+            get_move_columns(played_move, captured_piece);
+        self.conn
+            .execute(
+                "INSERT INTO Move(turn, move_notation, player, game_id, from_square, to_square,
+                 promotion, captured_piece, fen, votes)
+                 VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9,
+                 (SELECT votes FROM Vote WHERE move_notation = ?2))",
+                params![
+                    game.turn_number,
+                    game.previous_move.as_str(),
+                    player,
+                    game.id.to_string(),
+                    from_square,
+                    to_square,
+                    promotion,
+                    captured_piece,
+                    get_fen(game),
+                ],
             )
             .await
             .expect("Could not insert move");
+        let move_id = self.conn.last_insert_rowid();
+        self.conn
+            .execute(
+                "INSERT INTO PlayedVote(move_id, move_notation, votes)
+                 SELECT ?1, move_notation, votes FROM Vote",
+                params![move_id],
+            )
+            .await
+            .expect("Could not keep the votes of the move");
+        self.conn
+            .execute(
+                "INSERT INTO PlayedBallot(move_id, moves) SELECT ?1, moves FROM Ballot",
+                params![move_id],
+            )
+            .await
+            .expect("Could not keep the ballots of the move");
+        self.clear_votes().await;
+
+        move_id
+    }
+    // the engine's best move in the position the community played the move in
+    pub async fn set_engine_move(&self, move_id: i64, engine_move: chess_move::Move) {
+        self.conn
+            .execute(
+                "UPDATE Move SET engine_move = ?1 WHERE move_id = ?2",
+                params![engine_move.to_uci(), move_id],
+            )
+            .await
+            .expect("Could not set the engine's move");
+    }
+    // deletes the last move of the game and restores the votes of its turn, returns false if the
+    // game has no moves
+    pub async fn take_back_move(&self, id: &str) -> bool {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT move_id FROM Move WHERE game_id = ?1 ORDER BY turn DESC, move_id DESC
+                 LIMIT 1",
+                params![id],
+            )
+            .await
+            .expect("Could not get the last move");
+        let Some(row) = rows.next().await.unwrap() else {
+            return false;
+        };
+        let move_id: i64 = row.get(0).unwrap();
+
+        self.clear_votes().await;
+        self.conn
+            .execute(
+                "INSERT INTO Vote(move_notation, votes)
+                 SELECT move_notation, votes FROM PlayedVote WHERE move_id = ?1",
+                params![move_id],
+            )
+            .await
+            .expect("Could not restore votes");
+        self.conn
+            .execute(
+                "INSERT INTO Ballot(moves) SELECT moves FROM PlayedBallot WHERE move_id = ?1",
+                params![move_id],
+            )
+            .await
+            .expect("Could not restore ballots");
+        self.conn
+            .execute(
+                "INSERT INTO Voter(voter_hash) SELECT DISTINCT VoteLog.voter_hash FROM VoteLog
+                 JOIN Move ON VoteLog.game_id = Move.game_id AND VoteLog.turn = Move.turn
+                 AND VoteLog.player = Move.player WHERE Move.move_id = ?1",
+                params![move_id],
+            )
+            .await
+            .expect("Could not restore voters");
+        self.conn
+            .execute(
+                "DELETE FROM PlayedVote WHERE move_id = ?1",
+                params![move_id],
+            )
+            .await
+            .expect("Could not delete the votes of the move");
+        self.conn
+            .execute(
+                "DELETE FROM PlayedBallot WHERE move_id = ?1",
+                params![move_id],
+            )
+            .await
+            .expect("Could not delete the ballots of the move");
+        self.conn
+            .execute("DELETE FROM Move WHERE move_id = ?1", params![move_id])
+            .await
+            .expect("Could not delete move");
+
+        true
     }
     pub async fn get_moves(&self, id: &str) -> Vec<Move> {
         let mut rows: Rows = self
             .conn
             .query(
-                "SELECT * FROM Move WHERE game_id = ?1 ORDER BY turn",
+                "SELECT * FROM Move WHERE game_id = ?1 ORDER BY turn, move_id",
                 params![id],
             )
             .await
@@ -119,6 +842,114 @@ impl DB {
             .await
             .expect("Could not vote for a move");
     }
+    // remembers that the voter voted for the current move, returns false if they already did
+    pub async fn add_voter(&self, voter_hash: &str) -> bool {
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO Voter(voter_hash) VALUES(?1)",
+                params![voter_hash],
+            )
+            .await
+            .expect("Could not add voter")
+            == 1
+    }
+    pub async fn log_vote(
+        &self,
+        voter_hash: &str,
+        id: &str,
+        turn: u32,
+        player: &str,
+        ballot: &[String],
+    ) {
+        // the first choice counts for the distribution of the votes, the whole ballot is kept for
+        // the other voting methods
+        self.conn
+            .execute(
+                "INSERT INTO VoteLog(voter_hash, game_id, turn, player, move_notation, ballot)
+                 VALUES(?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    voter_hash,
+                    id,
+                    turn,
+                    player,
+                    ballot[0].as_str(),
+                    ballot.join(" ")
+                ],
+            )
+            .await
+            .expect("Could not log vote");
+    }
+    // the moves of each turn are sorted by their votes
+    pub async fn get_turn_votes(&self, id: &str) -> Vec<TurnVotes> {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT turn, player, move_notation, COUNT(*) AS votes FROM VoteLog
+                 WHERE game_id = ?1 GROUP BY turn, player, move_notation
+                 ORDER BY turn, player DESC, votes DESC, move_notation",
+                params![id],
+            )
+            .await
+            .expect("Could not get logged votes");
+
+        let mut turn_votes: Vec<TurnVotes> = vec![];
+        while let Some(row) = rows.next().await.unwrap() {
+            let logged_votes = de::from_row::<LoggedVotes>(&row).unwrap();
+            let vote = Vote {
+                move_notation: logged_votes.move_notation,
+                votes: logged_votes.votes,
+            };
+            match turn_votes.last_mut() {
+                Some(last)
+                    if last.turn == logged_votes.turn && last.player == logged_votes.player =>
+                {
+                    last.votes.push(vote)
+                }
+                _ => turn_votes.push(TurnVotes {
+                    turn: logged_votes.turn,
+                    player: logged_votes.player,
+                    votes: vec![vote],
+                }),
+            }
+        }
+
+        turn_votes
+    }
+    // groups the votes by the period the timestamps are formatted to, e.g. %Y-%m-%d for days
+    pub async fn get_turnout(&self, period_format: &str) -> Vec<Turnout> {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT strftime(?1, voted_at) AS period, COUNT(*) AS votes,
+                 COUNT(DISTINCT voter_hash) AS voters FROM VoteLog
+                 GROUP BY period ORDER BY period",
+                params![period_format],
+            )
+            .await
+            .expect("Could not get turnout");
+
+        let mut turnout: Vec<Turnout> = vec![];
+        while let Some(row) = rows.next().await.unwrap() {
+            turnout.push(de::from_row::<Turnout>(&row).unwrap());
+        }
+
+        turnout
+    }
+    pub async fn get_engine_agreement(&self) -> EngineAgreement {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT COUNT(*) AS community_moves,
+                 COALESCE(SUM(engine_move = from_square || to_square || COALESCE(lower(promotion), '')), 0)
+                 AS engine_moves
+                 FROM Move WHERE engine_move IS NOT NULL",
+                (),
+            )
+            .await
+            .expect("Could not get engine agreement");
+
+        de::from_row::<EngineAgreement>(&rows.next().await.unwrap().unwrap()).unwrap()
+    }
     pub async fn get_votes(&self) -> Vec<Vote> {
         let mut rows: Rows = self
             .conn
@@ -133,4 +964,204 @@ impl DB {
 
         votes
     }
+    // the moves of a ballot are stored in order, separated by spaces
+    pub async fn add_ballot(&self, ballot: &[String]) {
+        self.conn
+            .execute(
+                "INSERT INTO Ballot(moves) VALUES(?1)",
+                params![ballot.join(" ")],
+            )
+            .await
+            .expect("Could not add ballot");
+    }
+    pub async fn get_ballots(&self) -> Vec<Vec<String>> {
+        let mut rows: Rows = self
+            .conn
+            .query("SELECT moves FROM Ballot ORDER BY ballot_id", ())
+            .await
+            .expect("Could not get ballots");
+
+        let mut ballots: Vec<Vec<String>> = vec![];
+        while let Some(row) = rows.next().await.unwrap() {
+            let moves: String = row.get(0).unwrap();
+            ballots.push(moves.split_whitespace().map(String::from).collect());
+        }
+
+        ballots
+    }
+    // joining a team twice changes nothing
+    pub async fn join_team(&self, voter_hash: &str, team: &str) {
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO TeamMember(voter_hash, team) VALUES(?1, ?2)",
+                params![voter_hash, team],
+            )
+            .await
+            .expect("Could not join team");
+    }
+    pub async fn get_voter_teams(&self, voter_hash: &str) -> Vec<String> {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT team FROM TeamMember WHERE voter_hash = ?1",
+                params![voter_hash],
+            )
+            .await
+            .expect("Could not get the teams of the voter");
+
+        let mut teams: Vec<String> = vec![];
+        while let Some(row) = rows.next().await.unwrap() {
+            teams.push(row.get(0).unwrap());
+        }
+
+        teams
+    }
+    pub async fn count_team_members(&self, team: &str) -> u32 {
+        let mut rows: Rows = self
+            .conn
+            .query(
+                "SELECT COUNT(*) FROM TeamMember WHERE team = ?1",
+                params![team],
+            )
+            .await
+            .expect("Could not count team members");
+
+        rows.next()
+            .await
+            .unwrap()
+            .map_or(0, |row| row.get(0).unwrap())
+    }
+    pub async fn clear_votes(&self) {
+        self.conn
+            .execute("DELETE FROM Vote", ())
+            .await
+            .expect("Could not clear votes");
+        self.conn
+            .execute("DELETE FROM Ballot", ())
+            .await
+            .expect("Could not clear ballots");
+        self.conn
+            .execute("DELETE FROM Voter", ())
+            .await
+            .expect("Could not clear voters");
+    }
+}
+
+#[cfg(test)]
+mod test_db {
+    use libsql::{Builder, Connection};
+
+    use crate::{
+        db::{get_move_columns, get_schema_version, seed_db, Move, DB, MIGRATIONS},
+        game::{chess_move, fen::game_from_fen, Game},
+        voting::VotingMethod,
+    };
+
+    async fn connect_memory_db() -> Connection {
+        Builder::new_local(":memory:")
+            .build()
+            .await
+            .unwrap()
+            .connect()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_seed_keeps_games() {
+        actix_web::rt::System::new().block_on(async {
+            let conn = connect_memory_db().await;
+            seed_db(&conn).await;
+            let db = DB { conn };
+            db.create_game(
+                &Game::new(),
+                None,
+                None,
+                None,
+                VotingMethod::PLURALITY,
+                "00:00",
+            )
+            .await;
+
+            // a restart seeds the database again
+            seed_db(&db.conn).await;
+            assert_eq!(db.count_games().await, 1);
+            assert_eq!(
+                get_schema_version(&db.conn).await as usize,
+                MIGRATIONS.len() + 1
+            );
+        });
+    }
+
+    #[test]
+    fn test_migrate_moves() {
+        actix_web::rt::System::new().block_on(async {
+            // the first versioned schema, before the moves had their squares
+            let conn = connect_memory_db().await;
+            conn.execute_batch(
+                r#"
+            PRAGMA user_version = 1;
+            CREATE TABLE Game(game_id VARCHAR(255) PRIMARY KEY, mode VARCHAR(20));
+            CREATE TABLE Move(
+            move_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id VARCHAR(255),
+            turn INTEGER,
+            player VARCHAR(10),
+            move_notation VARCHAR(10)
+            );
+            INSERT INTO Game VALUES('1', 'COMMUNITY');
+            INSERT INTO Move(game_id, turn, player, move_notation) VALUES
+            ('1', 1, 'WHITE', 'e4'), ('1', 1, 'BLACK', 'd5'), ('1', 2, 'WHITE', 'exd5');
+            "#,
+            )
+            .await
+            .unwrap();
+
+            seed_db(&conn).await;
+            let moves = DB { conn }.get_moves("1").await;
+            assert_eq!(moves.len(), 3);
+            assert_eq!((&*moves[2].from_square, &*moves[2].to_square), ("e4", "d5"));
+            assert_eq!(moves[2].captured_piece.as_deref(), Some("P"));
+            assert_eq!(
+                moves[2].fen,
+                "rnbqkbnr/ppp1pppp/8/3P4/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2"
+            );
+            assert_eq!(moves[2].votes, None);
+        });
+    }
+
+    #[test]
+    fn test_stored_move() {
+        let game = game_from_fen("r3k3/1P6/8/8/8/8/8/4K3 w q - 0 1").unwrap();
+        let played_move = chess_move::Move::from_uci("b7a8q").unwrap();
+        let captured_piece = game.get_captured_piece(played_move);
+        let (from_square, to_square, promotion, captured_piece) =
+            get_move_columns(played_move, captured_piece);
+        assert_eq!(
+            (&*from_square, &*to_square, promotion.as_deref()),
+            ("b7", "a8", Some("Q"))
+        );
+        assert_eq!(captured_piece.as_deref(), Some("R"));
+
+        let stored_move = Move {
+            move_notation: "bxa8=Q+".to_string(),
+            turn: 1,
+            player: "WHITE".to_string(),
+            from_square,
+            to_square,
+            promotion,
+            captured_piece,
+            fen: "Q3k3/8/8/8/8/8/8/4K3 b - - 0 1".to_string(),
+            votes: None,
+            engine_move: None,
+            played_at: "2024-05-01 00:00:00".to_string(),
+        };
+        assert_eq!(stored_move.get_chess_move(), Ok(played_move));
+
+        // a promotion piece that doesn't exist means the history was tampered with
+        let stored_move = Move {
+            promotion: Some("K".to_string()),
+            ..stored_move
+        };
+        assert!(stored_move.get_chess_move().is_err());
+    }
 }
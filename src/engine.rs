@@ -0,0 +1,593 @@
+pub mod evaluation;
+pub mod uci;
+
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{chess_move::Move, chess_piece::Color, move_gen::get_legal_moves, Game};
+
+use self::evaluation::{evaluate, get_piece_value};
+
+/// Score of a position where the side to move is mated. Mates found further down the tree score
+/// a little less, so the engine prefers the quickest mate and delays being mated.
+pub const MATE_SCORE: i32 = 100_000;
+pub const MAX_MATE_PLY: i32 = 1_000;
+const INFINITY: i32 = 1_000_000;
+// keeps the transposition table at a few dozen megabytes in long searches
+const MAX_TABLE_ENTRIES: usize = 1 << 20;
+
+/// How far the engine searches and how much it deviates from the best move.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchLimits {
+    pub depth: u32,
+    /// The search stops after this many nodes and plays the best move of the last finished depth.
+    pub nodes: Option<u64>,
+    /// Like the node limit, but for the time the search takes.
+    pub time: Option<Duration>,
+    /// Any move scoring at most this many centipawns below the best one may be played instead.
+    pub randomness: i32,
+}
+
+impl SearchLimits {
+    pub const fn depth(depth: u32) -> SearchLimits {
+        SearchLimits {
+            depth,
+            nodes: None,
+            time: None,
+            randomness: 0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Strength {
+    EASY,
+    MEDIUM,
+    HARD,
+    MAXIMUM,
+}
+
+impl Strength {
+    pub fn get_search_limits(&self) -> SearchLimits {
+        match self {
+            Strength::EASY => SearchLimits {
+                depth: 1,
+                nodes: Some(2_000),
+                time: None,
+                randomness: 150,
+            },
+            Strength::MEDIUM => SearchLimits {
+                depth: 2,
+                nodes: Some(20_000),
+                time: None,
+                randomness: 50,
+            },
+            Strength::HARD => SearchLimits {
+                depth: 4,
+                nodes: Some(200_000),
+                time: None,
+                randomness: 10,
+            },
+            Strength::MAXIMUM => SearchLimits {
+                depth: 6,
+                nodes: Some(1_000_000),
+                time: None,
+                randomness: 0,
+            },
+        }
+    }
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Strength::EASY => "EASY",
+            Strength::MEDIUM => "MEDIUM",
+            Strength::HARD => "HARD",
+            Strength::MAXIMUM => "MAXIMUM",
+        }
+    }
+}
+
+/// The built-in engine playing one side of a game against the community.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct EngineOpponent {
+    pub color: Color,
+    pub strength: Strength,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    /// None if the side to move has no legal moves.
+    pub best_move: Option<Move>,
+    /// In centipawns from the point of view of the side to move.
+    pub score: i32,
+    /// The deepest fully searched depth.
+    pub depth: u32,
+    pub nodes: u64,
+}
+
+/// Looks for the best move of the side to move with an alpha-beta search, deepening one ply at a
+/// time up to the depth limit. The best move of each iteration is searched first in the next one,
+/// which makes the deeper searches cut off a lot more. Positions reached again through another
+/// move order are looked up by their Zobrist hash in a transposition table instead of being
+/// searched twice. The game is left as it was.
+pub fn search(game: &mut Game, limits: SearchLimits) -> SearchResult {
+    search_until_stopped(game, limits, &AtomicBool::new(false))
+}
+
+/// Like `search`, but also stops once `stop` is set from another thread, e.g. when a UCI GUI
+/// sends `stop`.
+pub fn search_until_stopped(
+    game: &mut Game,
+    limits: SearchLimits,
+    stop: &AtomicBool,
+) -> SearchResult {
+    let mut searcher = Searcher {
+        nodes: 0,
+        node_limit: limits.nodes,
+        deadline: limits.time.map(|time| Instant::now() + time),
+        stop,
+        is_stopped: false,
+        randomness: limits.randomness.max(0),
+        table: HashMap::new(),
+    };
+    let mut result = SearchResult {
+        best_move: None,
+        score: 0,
+        depth: 0,
+        nodes: 0,
+    };
+    let mut candidates = vec![];
+
+    for depth in 1..=limits.depth.max(1) {
+        let Some(root) = searcher.search_root(game, depth, result.best_move) else {
+            // an unfinished depth is only used if there is nothing better
+            if result.best_move.is_none() {
+                result.best_move = get_legal_moves(game).first().copied();
+            }
+            break;
+        };
+        result = SearchResult {
+            best_move: root.best_move,
+            score: root.score,
+            depth,
+            nodes: searcher.nodes,
+        };
+        candidates = root.candidates;
+
+        // no need to look any deeper once a forced mate has been found
+        if root.best_move.is_none() || root.score.abs() >= MATE_SCORE - MAX_MATE_PLY {
+            break;
+        }
+    }
+    result.nodes = searcher.nodes;
+
+    // weaker settings pick any move that is close enough to the best one
+    if let Some(&(chess_move, score)) = candidates.choose(&mut rand::thread_rng()) {
+        result.best_move = Some(chess_move);
+        result.score = score;
+    }
+
+    result
+}
+
+struct Searcher<'a> {
+    nodes: u64,
+    node_limit: Option<u64>,
+    deadline: Option<Instant>,
+    stop: &'a AtomicBool,
+    // stays set once a limit was hit, so the rest of the tree is cut off as well
+    is_stopped: bool,
+    randomness: i32,
+    // results of earlier visits to a position by its hash, kept across the iterations
+    table: HashMap<u64, TableEntry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bound {
+    Exact,
+    // the score is at least this high, the search was cut off
+    Lower,
+    // no move reached alpha, so the score is at most this high
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TableEntry {
+    depth: u32,
+    // mate scores are stored relative to the position, not to the root
+    score: i32,
+    bound: Bound,
+    best_move: Option<Move>,
+}
+
+struct RootResult {
+    best_move: Option<Move>,
+    score: i32,
+    // moves scoring within the randomness of the best one
+    candidates: Vec<(Move, i32)>,
+}
+
+impl Searcher<'_> {
+    // returns None if a limit was hit before the depth was searched completely
+    fn search_root(
+        &mut self,
+        game: &mut Game,
+        depth: u32,
+        previous_best: Option<Move>,
+    ) -> Option<RootResult> {
+        let mut moves = get_legal_moves(game);
+        if moves.is_empty() {
+            return Some(RootResult {
+                best_move: None,
+                score: self.get_terminal_score(game, 0),
+                candidates: vec![],
+            });
+        }
+        order_moves(game, &mut moves, previous_best);
+
+        let mut alpha = -INFINITY;
+        let mut best_move = moves[0];
+        let mut scored_moves = vec![];
+        for chess_move in moves {
+            // the window is widened by the randomness so that close moves get exact scores
+            let undo = game.apply_move(chess_move);
+            let score = -self.alpha_beta(
+                game,
+                depth - 1,
+                1,
+                -INFINITY,
+                -alpha.saturating_sub(self.randomness),
+            );
+            game.unmake_move(&undo);
+            if self.is_out_of_limits() {
+                return None;
+            }
+
+            scored_moves.push((chess_move, score));
+            if score > alpha {
+                alpha = score;
+                best_move = chess_move;
+            }
+        }
+
+        let candidates = match self.randomness {
+            0 => vec![],
+            randomness => scored_moves
+                .into_iter()
+                .filter(|&(_, score)| score > alpha - randomness)
+                .collect(),
+        };
+
+        Some(RootResult {
+            best_move: Some(best_move),
+            score: alpha,
+            candidates,
+        })
+    }
+
+    fn alpha_beta(
+        &mut self,
+        game: &mut Game,
+        depth: u32,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        if let Some(score) = get_variant_win_score(game, ply) {
+            return score;
+        }
+        if depth == 0 {
+            return self.quiescence(game, ply, alpha, beta);
+        }
+        if self.is_out_of_limits() {
+            return 0;
+        }
+        self.nodes += 1;
+
+        let entry = self.table.get(&game.hash).copied();
+        if let Some(entry) = entry.filter(|entry| entry.depth >= depth) {
+            let score = from_table_score(entry.score, ply);
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return beta,
+                Bound::Upper if score <= alpha => return alpha,
+                _ => (),
+            }
+        }
+
+        let mut moves = get_legal_moves(game);
+        if moves.is_empty() {
+            return self.get_terminal_score(game, ply);
+        }
+        order_moves(game, &mut moves, entry.and_then(|entry| entry.best_move));
+
+        let original_alpha = alpha;
+        let mut best_move = None;
+        for chess_move in moves {
+            let undo = game.apply_move(chess_move);
+            let score = -self.alpha_beta(game, depth - 1, ply + 1, -beta, -alpha);
+            game.unmake_move(&undo);
+
+            if score >= beta {
+                self.store(game, depth, ply, beta, Bound::Lower, Some(chess_move));
+                return beta;
+            }
+            if score > alpha {
+                alpha = score;
+                best_move = Some(chess_move);
+            }
+        }
+
+        let bound = match alpha > original_alpha {
+            true => Bound::Exact,
+            false => Bound::Upper,
+        };
+        self.store(game, depth, ply, alpha, bound, best_move);
+
+        alpha
+    }
+
+    fn store(
+        &mut self,
+        game: &Game,
+        depth: u32,
+        ply: i32,
+        score: i32,
+        bound: Bound,
+        best_move: Option<Move>,
+    ) {
+        // scores of an interrupted search are made up
+        if self.is_stopped
+            || (self.table.len() >= MAX_TABLE_ENTRIES && !self.table.contains_key(&game.hash))
+        {
+            return;
+        }
+        self.table.insert(
+            game.hash,
+            TableEntry {
+                depth,
+                score: to_table_score(score, ply),
+                bound,
+                best_move,
+            },
+        );
+    }
+
+    // keeps searching captures and promotions until the position is quiet, so that the
+    // evaluation is not taken in the middle of an exchange
+    fn quiescence(&mut self, game: &mut Game, ply: i32, mut alpha: i32, beta: i32) -> i32 {
+        if let Some(score) = get_variant_win_score(game, ply) {
+            return score;
+        }
+        if self.is_out_of_limits() {
+            return 0;
+        }
+        self.nodes += 1;
+
+        let stand_pat = evaluate(game);
+        if stand_pat >= beta {
+            return beta;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut moves: Vec<Move> = get_legal_moves(game)
+            .into_iter()
+            .filter(|chess_move| is_capture(game, *chess_move) || chess_move.promotion.is_some())
+            .collect();
+        order_moves(game, &mut moves, None);
+
+        for chess_move in moves {
+            let undo = game.apply_move(chess_move);
+            let score = -self.quiescence(game, ply + 1, -beta, -alpha);
+            game.unmake_move(&undo);
+
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+
+    // the clock and the stop flag are only looked at every few nodes, which is often enough
+    fn is_out_of_limits(&mut self) -> bool {
+        if !self.is_stopped {
+            self.is_stopped = self
+                .node_limit
+                .is_some_and(|node_limit| self.nodes >= node_limit)
+                || (self.nodes.is_multiple_of(1024)
+                    && (self.stop.load(Ordering::Relaxed)
+                        || self
+                            .deadline
+                            .is_some_and(|deadline| Instant::now() >= deadline)));
+        }
+
+        self.is_stopped
+    }
+
+    fn get_terminal_score(&self, game: &Game, ply: i32) -> i32 {
+        if game.get_rules().is_in_check(game) {
+            -MATE_SCORE + ply
+        } else {
+            // stalemate
+            0
+        }
+    }
+}
+
+// scores a position in which a win condition of the variant was reached like a mate, usually by
+// the side that just moved
+fn get_variant_win_score(game: &Game, ply: i32) -> Option<i32> {
+    game.get_rules()
+        .get_winner(game)
+        .map(|winner| match winner == game.next_to_move {
+            true => MATE_SCORE - ply,
+            false => -MATE_SCORE + ply,
+        })
+}
+
+// mates are counted from the root in the search, but from the position itself in the table, so
+// that they are still right when the position is reached at another ply
+fn to_table_score(score: i32, ply: i32) -> i32 {
+    match score {
+        score if score >= MATE_SCORE - MAX_MATE_PLY => score + ply,
+        score if score <= -MATE_SCORE + MAX_MATE_PLY => score - ply,
+        score => score,
+    }
+}
+
+fn from_table_score(score: i32, ply: i32) -> i32 {
+    match score {
+        score if score >= MATE_SCORE - MAX_MATE_PLY => score - ply,
+        score if score <= -MATE_SCORE + MAX_MATE_PLY => score + ply,
+        score => score,
+    }
+}
+
+fn is_capture(game: &Game, chess_move: Move) -> bool {
+    game.get_captured_piece(chess_move).is_some()
+}
+
+// searches the given move first, then captures of valuable pieces by cheap ones and promotions
+fn order_moves(game: &Game, moves: &mut [Move], first: Option<Move>) {
+    moves.sort_by_cached_key(|&chess_move| {
+        if Some(chess_move) == first {
+            return Reverse(INFINITY);
+        }
+
+        let mut score = 0;
+        if let Some(captured) = game
+            .piece_at(chess_move.to)
+            .filter(|captured| captured.color != game.next_to_move)
+        {
+            score += 10 * get_piece_value(captured.piece);
+            score -= get_piece_value(game.piece_at(chess_move.from).unwrap().piece) / 10;
+        }
+        if let Some(promotion) = chess_move.promotion {
+            score += get_piece_value(promotion);
+        }
+
+        Reverse(score)
+    });
+}
+
+#[cfg(test)]
+mod test_engine {
+    use crate::{
+        engine::{search, SearchLimits, MATE_SCORE},
+        game::{
+            chess_move::Move, fen::game_from_fen, fen::game_from_variant_fen, fen::get_fen,
+            variant::Variant, Game,
+        },
+    };
+
+    #[test]
+    fn test_finds_mate_in_one() {
+        let mut game = game_from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let result = search(&mut game, SearchLimits::depth(3));
+
+        assert_eq!(result.best_move, Some(Move::from_uci("a1a8").unwrap()));
+        assert_eq!(result.score, MATE_SCORE - 1);
+    }
+
+    #[test]
+    fn test_table_keeps_mate_distance() {
+        // Rb7 and Ra8 mate, the mate found through the table has to stay two moves away
+        let mut game = game_from_fen("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1").unwrap();
+        let result = search(&mut game, SearchLimits::depth(5));
+
+        assert_eq!(result.score, MATE_SCORE - 3);
+    }
+
+    #[test]
+    fn test_finds_variant_win() {
+        // the king walks onto the hill instead of taking the rook
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/4Kr2/8/8 w - - 0 1", Variant::KingOfTheHill)
+                .unwrap();
+        let result = search(&mut game, SearchLimits::depth(3));
+
+        let hill = [
+            Move::from_uci("e3d4").unwrap(),
+            Move::from_uci("e3e4").unwrap(),
+        ];
+        assert!(hill.contains(&result.best_move.unwrap()));
+        assert_eq!(result.score, MATE_SCORE - 1);
+    }
+
+    #[test]
+    fn test_captures_hanging_queen() {
+        let mut game = game_from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+        let result = search(&mut game, SearchLimits::depth(2));
+
+        assert_eq!(result.best_move, Some(Move::from_uci("d2d5").unwrap()));
+    }
+
+    #[test]
+    fn test_avoids_losing_the_queen() {
+        // taking the defended pawn loses the queen to the recapture
+        let mut game = game_from_fen("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1").unwrap();
+        let result = search(&mut game, SearchLimits::depth(1));
+
+        assert_ne!(result.best_move, Some(Move::from_uci("d1d5").unwrap()));
+    }
+
+    #[test]
+    fn test_no_moves_when_mated() {
+        let mut game = game_from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        let result = search(&mut game, SearchLimits::depth(3));
+
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, -MATE_SCORE);
+    }
+
+    #[test]
+    fn test_search_leaves_game_untouched() {
+        let mut game = Game::new();
+        let fen = get_fen(&game);
+        let result = search(&mut game, SearchLimits::depth(3));
+
+        assert!(result.best_move.is_some());
+        assert_eq!(result.depth, 3);
+        assert_eq!(get_fen(&game), fen);
+    }
+
+    #[test]
+    fn test_node_limit() {
+        let mut game = Game::new();
+        let limits = SearchLimits {
+            depth: 20,
+            nodes: Some(5_000),
+            time: None,
+            randomness: 0,
+        };
+        let result = search(&mut game, limits);
+
+        assert!(result.best_move.is_some());
+        assert!(result.depth < 20);
+        assert!(result.nodes <= 5_000);
+    }
+
+    #[test]
+    fn test_randomness_stays_close_to_best_move() {
+        // only taking the queen keeps the material even, every other move loses it
+        let limits = SearchLimits {
+            depth: 2,
+            nodes: None,
+            time: None,
+            randomness: 100,
+        };
+        for _ in 0..10 {
+            let mut game = game_from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+            let result = search(&mut game, limits);
+            assert_eq!(result.best_move, Some(Move::from_uci("d2d5").unwrap()));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::game::{
+    chess_piece::{ChessPiece, Color, Piece},
+    Game,
+};
+
+// Piece-square tables from white's point of view, laid out like `Field` so that row 0 is the 8th
+// rank. Black uses the same tables mirrored vertically. The values are the well known ones from
+// Tomasz Michniewski's simplified evaluation function.
+#[rustfmt::skip]
+const PAWN_TABLE: [[i32; 8]; 8] = [
+    [  0,   0,   0,   0,   0,   0,   0,   0],
+    [ 50,  50,  50,  50,  50,  50,  50,  50],
+    [ 10,  10,  20,  30,  30,  20,  10,  10],
+    [  5,   5,  10,  25,  25,  10,   5,   5],
+    [  0,   0,   0,  20,  20,   0,   0,   0],
+    [  5,  -5, -10,   0,   0, -10,  -5,   5],
+    [  5,  10,  10, -20, -20,  10,  10,   5],
+    [  0,   0,   0,   0,   0,   0,   0,   0],
+];
+#[rustfmt::skip]
+const KNIGHT_TABLE: [[i32; 8]; 8] = [
+    [-50, -40, -30, -30, -30, -30, -40, -50],
+    [-40, -20,   0,   0,   0,   0, -20, -40],
+    [-30,   0,  10,  15,  15,  10,   0, -30],
+    [-30,   5,  15,  20,  20,  15,   5, -30],
+    [-30,   0,  15,  20,  20,  15,   0, -30],
+    [-30,   5,  10,  15,  15,  10,   5, -30],
+    [-40, -20,   0,   5,   5,   0, -20, -40],
+    [-50, -40, -30, -30, -30, -30, -40, -50],
+];
+#[rustfmt::skip]
+const BISHOP_TABLE: [[i32; 8]; 8] = [
+    [-20, -10, -10, -10, -10, -10, -10, -20],
+    [-10,   0,   0,   0,   0,   0,   0, -10],
+    [-10,   0,   5,  10,  10,   5,   0, -10],
+    [-10,   5,   5,  10,  10,   5,   5, -10],
+    [-10,   0,  10,  10,  10,  10,   0, -10],
+    [-10,  10,  10,  10,  10,  10,  10, -10],
+    [-10,   5,   0,   0,   0,   0,   5, -10],
+    [-20, -10, -10, -10, -10, -10, -10, -20],
+];
+#[rustfmt::skip]
+const ROOK_TABLE: [[i32; 8]; 8] = [
+    [  0,   0,   0,   0,   0,   0,   0,   0],
+    [  5,  10,  10,  10,  10,  10,  10,   5],
+    [ -5,   0,   0,   0,   0,   0,   0,  -5],
+    [ -5,   0,   0,   0,   0,   0,   0,  -5],
+    [ -5,   0,   0,   0,   0,   0,   0,  -5],
+    [ -5,   0,   0,   0,   0,   0,   0,  -5],
+    [ -5,   0,   0,   0,   0,   0,   0,  -5],
+    [  0,   0,   0,   5,   5,   0,   0,   0],
+];
+#[rustfmt::skip]
+const QUEEN_TABLE: [[i32; 8]; 8] = [
+    [-20, -10, -10,  -5,  -5, -10, -10, -20],
+    [-10,   0,   0,   0,   0,   0,   0, -10],
+    [-10,   0,   5,   5,   5,   5,   0, -10],
+    [ -5,   0,   5,   5,   5,   5,   0,  -5],
+    [  0,   0,   5,   5,   5,   5,   0,  -5],
+    [-10,   5,   5,   5,   5,   5,   0, -10],
+    [-10,   0,   5,   0,   0,   0,   0, -10],
+    [-20, -10, -10,  -5,  -5, -10, -10, -20],
+];
+#[rustfmt::skip]
+const KING_TABLE: [[i32; 8]; 8] = [
+    [-30, -40, -40, -50, -50, -40, -40, -30],
+    [-30, -40, -40, -50, -50, -40, -40, -30],
+    [-30, -40, -40, -50, -50, -40, -40, -30],
+    [-30, -40, -40, -50, -50, -40, -40, -30],
+    [-20, -30, -30, -40, -40, -30, -30, -20],
+    [-10, -20, -20, -20, -20, -20, -20, -10],
+    [ 20,  20,   0,   0,   0,   0,  20,  20],
+    [ 20,  30,  10,   0,   0,  10,  30,  20],
+];
+
+/// The material value of a piece in centipawns. The king can never be traded, so it is only given
+/// a value to order captures by.
+pub fn get_piece_value(piece: Piece) -> i32 {
+    match piece {
+        Piece::PAWN => 100,
+        Piece::KNIGHT => 320,
+        Piece::BISHOP => 330,
+        Piece::ROOK => 500,
+        Piece::QUEEN => 900,
+        Piece::KING => 20000,
+    }
+}
+
+/// Scores the position in centipawns from the point of view of the side to move, by adding up
+/// material and piece-square bonuses.
+pub fn evaluate(game: &Game) -> i32 {
+    let mut score = 0;
+
+    for (row, squares) in game.field.iter().enumerate() {
+        for (col, square) in squares.iter().enumerate() {
+            if let Some(chess_piece) = square {
+                let value = get_square_value(*chess_piece, row, col);
+                match chess_piece.color {
+                    Color::WHITE => score += value,
+                    Color::BLACK => score -= value,
+                }
+            }
+        }
+    }
+
+    match game.next_to_move {
+        Color::WHITE => score,
+        Color::BLACK => -score,
+    }
+}
+
+fn get_square_value(chess_piece: ChessPiece, row: usize, col: usize) -> i32 {
+    let row = match chess_piece.color {
+        Color::WHITE => row,
+        Color::BLACK => 7 - row,
+    };
+    let table = match chess_piece.piece {
+        Piece::PAWN => &PAWN_TABLE,
+        Piece::KNIGHT => &KNIGHT_TABLE,
+        Piece::BISHOP => &BISHOP_TABLE,
+        Piece::ROOK => &ROOK_TABLE,
+        Piece::QUEEN => &QUEEN_TABLE,
+        Piece::KING => return KING_TABLE[row][col],
+    };
+
+    get_piece_value(chess_piece.piece) + table[row][col]
+}
+
+#[cfg(test)]
+mod test_evaluation {
+    use crate::{
+        engine::evaluation::evaluate,
+        game::{chess_move::Move, fen::game_from_fen, Game},
+    };
+
+    #[test]
+    fn test_starting_position_is_equal() {
+        let mut game = Game::new();
+        assert_eq!(evaluate(&game), 0);
+
+        game.validate_and_make_move(Move::from_uci("e2e4").unwrap())
+            .unwrap();
+        game.validate_and_make_move(Move::from_uci("e7e5").unwrap())
+            .unwrap();
+        assert_eq!(evaluate(&game), 0);
+    }
+
+    #[test]
+    fn test_evaluation_is_from_side_to_move() {
+        // white is a queen up
+        let white_to_move = game_from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        let black_to_move = game_from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap();
+
+        assert!(evaluate(&white_to_move) > 800);
+        assert_eq!(evaluate(&black_to_move), -evaluate(&white_to_move));
+    }
+}
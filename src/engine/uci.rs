@@ -0,0 +1,336 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    engine::{SearchLimits, SearchResult, MATE_SCORE},
+    game::{chess_move::Move, fen::get_fen, variant::Variant, Game},
+    utils::error::{UCI_ENGINE_ERROR, UCI_ENGINE_START_ERROR},
+};
+
+// how long the engine may take to answer anything but a search
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+// how long a search without a time limit may take
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(60);
+// how long the engine may take to answer stop with its move
+const STOP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A locally installed engine like Stockfish, driven over the UCI protocol through its stdin and
+/// stdout. Positions are handed over as FEN, so the engine does not need to know the history.
+/// An engine that doesn't answer in time is killed, so it can't keep its callers waiting forever.
+pub struct UciEngine {
+    process: Child,
+    stdin: ChildStdin,
+    // the lines of the engine's stdout, which is read on its own thread so that reads can time out
+    lines: Receiver<String>,
+    pub name: Option<String>,
+}
+
+impl UciEngine {
+    /// Starts the engine and waits until it is ready to search.
+    pub fn start(program: &str, args: &[&str]) -> Result<UciEngine, &'static str> {
+        let mut process = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|_| UCI_ENGINE_START_ERROR)?;
+        let stdin = process.stdin.take().ok_or(UCI_ENGINE_START_ERROR)?;
+        let stdout = BufReader::new(process.stdout.take().ok_or(UCI_ENGINE_START_ERROR)?);
+        // the thread ends once the engine closes its output
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if sender.send(line.trim().to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut engine = UciEngine {
+            process,
+            stdin,
+            lines,
+            name: None,
+        };
+        engine.send("uci")?;
+        let deadline = Instant::now() + RESPONSE_TIMEOUT;
+        loop {
+            let line = engine.read_line(deadline)?;
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.name = Some(name.to_string());
+            }
+            if line == "uciok" {
+                break;
+            }
+        }
+        engine.wait_until_ready()?;
+
+        Ok(engine)
+    }
+    /// Searches the position of the game within the depth, node and time limits. The randomness of the
+    /// limits is up to the engine's own settings and not passed on. The search is stopped once it
+    /// takes longer than the time limit, or a minute without one, and the engine is killed if it
+    /// doesn't come up with a move after that.
+    pub fn search(
+        &mut self,
+        game: &Game,
+        limits: SearchLimits,
+    ) -> Result<SearchResult, &'static str> {
+        // in Chess960 castling moves are given as the king taking its own rook
+        self.send(&format!(
+            "setoption name UCI_Chess960 value {}",
+            game.variant == Variant::Chess960
+        ))?;
+        self.send("ucinewgame")?;
+        self.wait_until_ready()?;
+        self.send(&format!("position fen {}", get_fen(game)))?;
+
+        let mut go = format!("go depth {}", limits.depth);
+        if let Some(nodes) = limits.nodes {
+            go.push_str(&format!(" nodes {}", nodes));
+        }
+        if let Some(time) = limits.time {
+            go.push_str(&format!(" movetime {}", time.as_millis()));
+        }
+        self.send(&go)?;
+        let mut deadline = Instant::now() + limits.time.unwrap_or(SEARCH_TIMEOUT);
+        let mut is_stopped = false;
+
+        let mut result = SearchResult {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+        };
+        loop {
+            let line = match self.read_line(deadline) {
+                Ok(line) => line,
+                Err(_) if !is_stopped && self.is_running() => {
+                    self.send("stop")?;
+                    deadline = Instant::now() + STOP_TIMEOUT;
+                    is_stopped = true;
+                    continue;
+                }
+                Err(e) => {
+                    self.kill();
+                    return Err(e);
+                }
+            };
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("info") => read_info(tokens, &mut result),
+                Some("bestmove") => {
+                    result.best_move = match tokens.next() {
+                        None | Some("(none)") => None,
+                        Some(uci) => Some(Move::from_uci(uci).map_err(|_| UCI_ENGINE_ERROR)?),
+                    };
+                    return Ok(result);
+                }
+                _ => (),
+            }
+        }
+    }
+    fn wait_until_ready(&mut self) -> Result<(), &'static str> {
+        self.send("isready")?;
+        let deadline = Instant::now() + RESPONSE_TIMEOUT;
+        loop {
+            match self.read_line(deadline) {
+                Ok(line) if line == "readyok" => return Ok(()),
+                Ok(_) => (),
+                Err(e) => {
+                    self.kill();
+                    return Err(e);
+                }
+            }
+        }
+    }
+    fn is_running(&mut self) -> bool {
+        self.process.try_wait().ok().flatten().is_none()
+    }
+    fn kill(&mut self) {
+        if self.is_running() {
+            let _ = self.process.kill();
+        }
+        let _ = self.process.wait();
+    }
+    fn send(&mut self, command: &str) -> Result<(), &'static str> {
+        writeln!(self.stdin, "{}", command).map_err(|_| UCI_ENGINE_ERROR)?;
+        self.stdin.flush().map_err(|_| UCI_ENGINE_ERROR)
+    }
+    // fails if the engine closed its output, most likely because it crashed, or if it didn't
+    // answer before the deadline
+    fn read_line(&mut self, deadline: Instant) -> Result<String, &'static str> {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        self.lines
+            .recv_timeout(timeout)
+            .map_err(|_| UCI_ENGINE_ERROR)
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        self.kill();
+    }
+}
+
+// reads depth, nodes and score of an info line, scores are from the side to move like ours and
+// mates are converted to the same scale as the built-in engine
+fn read_info<'a>(mut tokens: impl Iterator<Item = &'a str>, result: &mut SearchResult) {
+    while let Some(token) = tokens.next() {
+        match token {
+            "depth" => result.depth = parse_next(&mut tokens).unwrap_or(result.depth),
+            "nodes" => result.nodes = parse_next(&mut tokens).unwrap_or(result.nodes),
+            "cp" => result.score = parse_next(&mut tokens).unwrap_or(result.score),
+            "mate" => match parse_next::<i32>(&mut tokens) {
+                Some(moves) if moves > 0 => result.score = MATE_SCORE - (2 * moves - 1),
+                Some(moves) => result.score = -MATE_SCORE - 2 * moves,
+                None => (),
+            },
+            // the rest of the line is the principal variation or a string
+            "pv" | "string" => return,
+            _ => (),
+        }
+    }
+}
+
+fn parse_next<'a, T: std::str::FromStr>(tokens: &mut impl Iterator<Item = &'a str>) -> Option<T> {
+    tokens.next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod test_uci {
+    use std::time::Duration;
+
+    use crate::{
+        engine::{uci::UciEngine, SearchLimits, MATE_SCORE},
+        game::{chess_move::Move, Game},
+    };
+
+    // answers like a real engine, with e2e4 for white and a mate for black
+    const STUB_ENGINE: &str = r#"
+        while read -r line; do
+            case "$line" in
+                uci) echo "id name Stub Engine"; echo "option name Hash type spin"; echo "uciok";;
+                isready) echo "readyok";;
+                "position fen"*" w "*) side=w;;
+                "position fen"*" b "*) side=b;;
+                go*)
+                    if [ "$side" = w ]; then
+                        echo "info depth 12 seldepth 15 score cp 31 nodes 51234 pv e2e4 e7e5"
+                        echo "bestmove e2e4 ponder e7e5"
+                    else
+                        echo "info depth 3 score mate -2 nodes 99 pv g8f6"
+                        echo "bestmove g8f6"
+                    fi;;
+                quit) exit 0;;
+            esac
+        done
+    "#;
+
+    fn start_stub_engine() -> UciEngine {
+        UciEngine::start("sh", &["-c", STUB_ENGINE]).expect("Expected stub engine to start")
+    }
+
+    #[test]
+    fn test_handshake() {
+        let engine = start_stub_engine();
+        assert_eq!(engine.name.as_deref(), Some("Stub Engine"));
+    }
+
+    #[test]
+    fn test_search() {
+        let mut engine = start_stub_engine();
+        let mut game = Game::new();
+
+        let result = engine.search(&game, SearchLimits::depth(12)).unwrap();
+        assert_eq!(result.best_move, Some(Move::from_uci("e2e4").unwrap()));
+        assert_eq!(result.score, 31);
+        assert_eq!(result.depth, 12);
+        assert_eq!(result.nodes, 51234);
+
+        game.validate_and_make_move(result.best_move.unwrap())
+            .unwrap();
+        let result = engine.search(&game, SearchLimits::depth(3)).unwrap();
+        assert_eq!(result.best_move, Some(Move::from_uci("g8f6").unwrap()));
+        assert_eq!(result.score, -MATE_SCORE + 4);
+    }
+
+    #[test]
+    fn test_stops_slow_engine() {
+        // only answers with a move once it is told to stop
+        let mut engine = UciEngine::start(
+            "sh",
+            &[
+                "-c",
+                r#"
+                while read -r line; do
+                    case "$line" in
+                        uci) echo "uciok";;
+                        isready) echo "readyok";;
+                        stop) echo "bestmove e2e4";;
+                    esac
+                done
+                "#,
+            ],
+        )
+        .unwrap();
+        let limits = SearchLimits {
+            time: Some(Duration::from_millis(100)),
+            ..SearchLimits::depth(20)
+        };
+
+        let result = engine.search(&Game::new(), limits).unwrap();
+        assert_eq!(result.best_move, Some(Move::from_uci("e2e4").unwrap()));
+    }
+
+    #[test]
+    fn test_kills_hanging_engine() {
+        let mut engine = UciEngine::start(
+            "sh",
+            &[
+                "-c",
+                r#"
+                while read -r line; do
+                    case "$line" in
+                        uci) echo "uciok";;
+                        isready) echo "readyok";;
+                    esac
+                done
+                "#,
+            ],
+        )
+        .unwrap();
+        let limits = SearchLimits {
+            time: Some(Duration::from_millis(100)),
+            ..SearchLimits::depth(20)
+        };
+
+        assert!(engine.search(&Game::new(), limits).is_err());
+        assert!(!engine.is_running());
+    }
+
+    #[test]
+    fn test_missing_engine() {
+        assert!(UciEngine::start("./no-such-engine", &[]).is_err());
+    }
+
+    #[test]
+    fn test_crashing_engine() {
+        let mut engine = UciEngine::start(
+            "sh",
+            &[
+                "-c",
+                "read -r line; echo uciok; read -r line; echo readyok; exit 1",
+            ],
+        )
+        .unwrap();
+        assert!(engine.search(&Game::new(), SearchLimits::depth(1)).is_err());
+    }
+}
@@ -1,36 +1,64 @@
+pub mod action;
+pub mod chess960;
+pub mod chess_move;
 pub mod chess_piece;
+pub mod fen;
+pub mod move_gen;
+pub mod opening;
+pub mod perft;
 pub mod validation;
+pub mod variant;
+pub mod zobrist;
 
 #[cfg(test)]
 mod full_game_tests;
 
+use crate::game::chess_move::{Move, Square};
 use crate::game::chess_piece::{ChessPiece, Color, Piece};
-use crate::utils::convert_notation::{get_promotion_piece, get_squares_from_notation};
-use crate::utils::error::{CHECK_ERROR, NO_PIECE_SELECTED_ERROR};
+use crate::utils::convert_notation::get_promotion_char;
+use crate::utils::error::{NO_PIECE_SELECTED_ERROR, OPPONENT_PIECE_SELECTED_ERROR};
+use serde::Serialize;
 use uuid::Uuid;
 
 use self::validation::bishop::validate_bishop_move;
-use self::validation::check_mate::{can_be_captured_by, can_king_be_captured_after_move, is_mate};
 use self::validation::king::validate_king_move;
 use self::validation::knight::validate_knight_move;
 use self::validation::pawn::validate_pawn_move;
 use self::validation::queen::validate_queen_move;
 use self::validation::rook::validate_rook_move;
+use self::variant::{Rules, Variant};
+use self::zobrist::{get_castling_key, get_en_passant_key, get_hash, get_piece_key, get_side_key};
+
+/// The board as a fixed 8x8 mailbox indexed by `[row][col]`, where row 0 is the 8th rank and
+/// col 0 is the a-file. Being a plain array it lives inline in `Game` and copies without
+/// allocating.
+pub type Field = [[Option<ChessPiece>; 8]; 8];
 
 #[derive(Clone, Debug)]
 pub struct Game {
     pub id: Uuid,
+    pub variant: Variant,
     pub game_result: Option<GameResult>,
+    // the side whose draw offer stands until the opponent moves
+    pub draw_offer: Option<Color>,
     pub turn_number: u32,
     pub next_to_move: Color,
     pub previous_move: String,
     pub can_castle: CastlingRights,
-    pub can_en_passant: bool,
+    // the files the rooks that can castle start on, shared by both sides since the black pieces
+    // mirror the white ones
+    pub castling_rook_cols: CastlingRookCols,
+    // the square a pawn passed over with its two square move, where it can be captured en passant
+    pub en_passant_square: Option<Square>,
     pub king_position: KingPosition,
-    pub field: Vec<Vec<Option<ChessPiece>>>,
+    // the checks each side has given, which decide Three-check games
+    pub checks_given: ChecksGiven,
+    pub field: Field,
+    // Zobrist hash of the position, kept up to date by `apply_move`
+    pub hash: u64,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct CastlingRights {
     pub white_can_short_castle: bool,
     pub white_can_long_castle: bool,
@@ -38,184 +66,499 @@ pub struct CastlingRights {
     pub black_can_long_castle: bool,
 }
 
-#[derive(Clone, Debug)]
+/// The a- and h-file in standard chess, anywhere around the king in Chess960.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CastlingRookCols {
+    pub short: usize,
+    pub long: usize,
+}
+
+impl CastlingRookCols {
+    pub const STANDARD: CastlingRookCols = CastlingRookCols { short: 7, long: 0 };
+}
+
+/// Where king and rook end up when castling, which is the same as in standard chess for every
+/// Chess960 position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Castling {
+    pub short: bool,
+    pub king_to: Square,
+    pub rook_from: Square,
+    pub rook_to: Square,
+}
+
+/// A side without a king, like white in Horde, keeps the square its king would start on, which
+/// the rules of the variant never look at.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct KingPosition {
-    pub white_king_position: (usize, usize),
-    pub black_king_position: (usize, usize),
+    pub white_king_position: Square,
+    pub black_king_position: Square,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChecksGiven {
+    pub white: u32,
+    pub black: u32,
+}
+
+/// Everything `apply_move` overwrites, so that `unmake_move` can restore the game without having
+/// to clone it beforehand.
+#[derive(Clone, Debug)]
+pub struct MoveUndo {
+    pub chess_move: Move,
+    pub moved_piece: ChessPiece,
+    pub captured_piece: Option<ChessPiece>,
+    // differs from the target square for en passant captures
+    pub captured_square: Square,
+    // king and rook both move when castling, and the king may end up where the rook stood
+    pub castling: Option<Castling>,
+    pub can_castle: CastlingRights,
+    pub en_passant_square: Option<Square>,
+    pub king_position: KingPosition,
+    pub checks_given: ChecksGiven,
+    // pieces the variant took off the board besides the captured one, like the explosions in
+    // Atomic
+    pub removed_pieces: Vec<(Square, ChessPiece)>,
+    pub hash: u64,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum GameResult {
     WhiteWon,
     BlackWon,
+    Draw,
+}
+
+impl GameResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWon => "1-0",
+            GameResult::BlackWon => "0-1",
+            GameResult::Draw => "1/2-1/2",
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Game {
     pub fn new() -> Game {
-        create_new_game()
+        create_new_game(BACK_RANK)
+    }
+    pub fn get_rules(&self) -> &'static dyn Rules {
+        self.variant.get_rules()
+    }
+    pub fn piece_at(&self, square: Square) -> Option<ChessPiece> {
+        self.field[square.row][square.col]
+    }
+    /// The piece the move captures, which is not on the target square for en passant.
+    pub fn get_captured_piece(&self, chess_move: Move) -> Option<Piece> {
+        let Move { from, to, .. } = chess_move;
+        let moving_piece = self.piece_at(from)?;
+        if moving_piece.piece == Piece::PAWN && from.col != to.col && self.piece_at(to).is_none() {
+            return self.field[from.row][to.col].map(|chess_piece| chess_piece.piece);
+        }
+
+        // castling as the king taking its own rook captures nothing
+        self.piece_at(to)
+            .filter(|chess_piece| chess_piece.color != moving_piece.color)
+            .map(|chess_piece| chess_piece.piece)
+    }
+    /// Returns how king and rook move if the move castles. Castling is written as the king taking
+    /// its own rook like in Chess960, or as the king moving two squares if king and rook start
+    /// where they do in standard chess.
+    pub fn get_castling(&self, chess_move: Move) -> Option<Castling> {
+        let Move { from, to, .. } = chess_move;
+        let king = self.piece_at(from)?;
+        let (home_row, can_short_castle, can_long_castle) = match king.color {
+            Color::WHITE => (
+                7,
+                self.can_castle.white_can_short_castle,
+                self.can_castle.white_can_long_castle,
+            ),
+            Color::BLACK => (
+                0,
+                self.can_castle.black_can_short_castle,
+                self.can_castle.black_can_long_castle,
+            ),
+        };
+        if king.piece != Piece::KING || from.row != home_row || to.row != home_row {
+            return None;
+        }
+
+        let rook = Some(ChessPiece {
+            piece: Piece::ROOK,
+            color: king.color,
+        });
+        [
+            (true, can_short_castle, self.castling_rook_cols.short),
+            (false, can_long_castle, self.castling_rook_cols.long),
+        ]
+        .into_iter()
+        .find_map(|(short, can_castle, rook_col)| {
+            let rook_from = Square::new(home_row, rook_col);
+            let is_castling_move = to == rook_from || to == self.get_castling_target(from, short);
+            if !can_castle || self.piece_at(rook_from) != rook || !is_castling_move {
+                return None;
+            }
+
+            let (king_col, rook_col) = if short { (6, 5) } else { (2, 3) };
+            Some(Castling {
+                short,
+                king_to: Square::new(home_row, king_col),
+                rook_from,
+                rook_to: Square::new(home_row, rook_col),
+            })
+        })
+    }
+    /// The square the king moves to when castling to the given side in generated moves: two
+    /// squares towards the rook in the standard setup, and the square of the rook otherwise,
+    /// since in Chess960 the king might not move at all or only by one square.
+    pub fn get_castling_target(&self, king: Square, short: bool) -> Square {
+        let (rook_col, corner_col, king_col) = match short {
+            true => (self.castling_rook_cols.short, 7, 6),
+            false => (self.castling_rook_cols.long, 0, 2),
+        };
+        if king.col == 4 && rook_col == corner_col {
+            Square::new(king.row, king_col)
+        } else {
+            Square::new(king.row, rook_col)
+        }
+    }
+    /// Whether the side to move or its opponent can castle in a way that only exists in
+    /// Chess960, so engines need to be told to expect it.
+    pub fn has_chess960_castling(&self) -> bool {
+        let kings = [
+            (
+                self.king_position.white_king_position,
+                self.can_castle.white_can_short_castle,
+                self.can_castle.white_can_long_castle,
+            ),
+            (
+                self.king_position.black_king_position,
+                self.can_castle.black_can_short_castle,
+                self.can_castle.black_can_long_castle,
+            ),
+        ];
+
+        kings
+            .into_iter()
+            .any(|(king, can_short_castle, can_long_castle)| {
+                (can_short_castle || can_long_castle) && king.col != 4
+                    || can_short_castle && self.castling_rook_cols.short != 7
+                    || can_long_castle && self.castling_rook_cols.long != 0
+            })
     }
-    pub fn validate_and_make_move(
-        &mut self,
-        algebraic_from: &str,
-        algebraic_to: &str,
-        promotion_ch: char,
-    ) -> Result<(), &'static str> {
-        self.validate_move(algebraic_from, algebraic_to, promotion_ch)?;
-        self.make_move(algebraic_from, algebraic_to, promotion_ch);
+    pub fn validate_and_make_move(&mut self, chess_move: Move) -> Result<(), &'static str> {
+        self.validate_move(chess_move)?;
+        self.make_move(chess_move);
 
         Ok(())
     }
-    pub fn validate_move(
-        &self,
-        algebraic_from: &str,
-        algebraic_to: &str,
-        promotion_ch: char,
-    ) -> Result<(), &'static str> {
-        let (from, to) = get_squares_from_notation(algebraic_from, algebraic_to)?;
+    pub fn validate_move(&mut self, chess_move: Move) -> Result<(), &'static str> {
+        let Move { from, to, .. } = chess_move;
 
         // check if the move is valid
-        match self.field[from.0][from.1] {
+        match self.piece_at(from) {
             None => return Err(NO_PIECE_SELECTED_ERROR),
+            Some(x) if x.color != self.next_to_move => return Err(OPPONENT_PIECE_SELECTED_ERROR),
             Some(x) => match x.piece {
-                Piece::BISHOP => validate_bishop_move(from, to, &self)?,
-                Piece::ROOK => validate_rook_move(from, to, &self)?,
-                Piece::QUEEN => validate_queen_move(from, to, &self)?,
-                Piece::KNIGHT => validate_knight_move(from, to, &self)?,
-                Piece::PAWN => validate_pawn_move(from, to, promotion_ch, &self)?,
-                Piece::KING => validate_king_move(from, to, &self)?,
+                Piece::BISHOP => validate_bishop_move(from, to, self)?,
+                Piece::ROOK => validate_rook_move(from, to, self)?,
+                Piece::QUEEN => validate_queen_move(from, to, self)?,
+                Piece::KNIGHT => validate_knight_move(from, to, self)?,
+                Piece::PAWN => validate_pawn_move(from, to, chess_move.promotion, self)?,
+                Piece::KING => validate_king_move(from, to, self)?,
             },
         };
 
-        // check if the move would put your king in check
-        if can_king_be_captured_after_move(&self, algebraic_from, algebraic_to, promotion_ch).len()
-            != 0
+        // check if the variant allows the move, e.g. that it doesn't put your king in check
+        self.get_rules().validate_move(self, chess_move)
+    }
+    pub fn make_move(&mut self, chess_move: Move) {
+        self.previous_move = self.get_move_notation(chess_move);
+        // moving instead of accepting declines the opponent's draw offer
+        if self
+            .draw_offer
+            .is_some_and(|color| color != self.next_to_move)
         {
-            return Err(CHECK_ERROR);
+            self.draw_offer = None;
         }
+        self.apply_move(chess_move);
+        let rules = self.get_rules();
 
-        Ok(())
-    }
-    pub fn make_move(&mut self, algebraic_from: &str, algebraic_to: &str, promotion_ch: char) {
-        // we can unwrap here since we perform this function in the validation function as well
-        let (from, to) = get_squares_from_notation(algebraic_from, algebraic_to).unwrap();
-        self.can_en_passant = false;
-
-        // Add x in case we capture
-        let move_with_capture = self.field[to.0][to.1].is_some();
-        if move_with_capture {
-            self.previous_move = "x".to_string();
-        } else {
-            self.previous_move = "".to_string();
+        // add check to notation if necessary
+        if rules.is_in_check(self) {
+            self.previous_move.push('+');
         }
 
-        // ugly, but we need to check for en passant before making the actual move
-        if self.field[to.0][to.1].is_none()
-            && self.field[from.0][from.1].unwrap().piece == Piece::PAWN
-            && from.1 != to.1
-        {
-            self.field[from.0][to.1] = None;
-            self.previous_move = "x".to_string();
+        // the side that just moved wins by mate or by a win condition of the variant
+        let winner = match rules.get_winner(self) {
+            Some(winner) => Some(winner),
+            None if rules.is_mate(self) => match self.next_to_move {
+                Color::WHITE => Some(Color::BLACK),
+                Color::BLACK => Some(Color::WHITE),
+            },
+            None => None,
+        };
+        match winner {
+            Some(Color::WHITE) => self.game_result = Some(GameResult::WhiteWon),
+            Some(Color::BLACK) => self.game_result = Some(GameResult::BlackWon),
+            None if rules.is_stalemate(self) => self.game_result = Some(GameResult::Draw),
+            None => (),
         }
+    }
+    /// Moves the pieces on the board and updates castling rights, en passant rights, king
+    /// positions and the side to move, without building notation or looking for a mate. The
+    /// returned record can be handed to `unmake_move` to take the move back, which lets
+    /// validation and search work on a single game instead of cloning it for every move.
+    pub fn apply_move(&mut self, chess_move: Move) -> MoveUndo {
+        let Move { from, to, .. } = chess_move;
+        // we can unwrap here since the move has been validated before
+        let moving_piece = self.piece_at(from).unwrap();
+        let castling = match moving_piece.piece {
+            Piece::KING => self.get_castling(chess_move),
+            _ => None,
+        };
+        let mut undo = MoveUndo {
+            chess_move,
+            moved_piece: moving_piece,
+            captured_piece: self.piece_at(to),
+            captured_square: to,
+            castling,
+            can_castle: self.can_castle,
+            en_passant_square: self.en_passant_square,
+            king_position: self.king_position,
+            checks_given: self.checks_given,
+            removed_pieces: vec![],
+            hash: self.hash,
+        };
+        self.hash ^= get_en_passant_key(self) ^ get_castling_key(self);
+        self.en_passant_square = None;
 
-        // Target square
-        self.previous_move.push_str(algebraic_to);
+        // en passant captures the pawn next to the moving one
+        if moving_piece.piece == Piece::PAWN && from.col != to.col && self.piece_at(to).is_none() {
+            undo.captured_square = Square::new(from.row, to.col);
+            undo.captured_piece = self.field[from.row][to.col];
+            self.field[from.row][to.col] = None;
+        }
 
         // move to new square
-        self.field[to.0][to.1] = self.field[from.0][from.1];
-        self.field[from.0][from.1] = None;
+        match castling {
+            Some(castling) => {
+                // the king might land where the rook stood or not move at all, so both are
+                // taken off the board before they are placed again
+                let rook = self.piece_at(castling.rook_from);
+                undo.captured_piece = None;
+                self.field[from.row][from.col] = None;
+                self.field[castling.rook_from.row][castling.rook_from.col] = None;
+                self.field[castling.king_to.row][castling.king_to.col] = Some(moving_piece);
+                self.field[castling.rook_to.row][castling.rook_to.col] = rook;
+            }
+            None => {
+                self.field[to.row][to.col] = Some(moving_piece);
+                self.field[from.row][from.col] = None;
+            }
+        }
 
         // for some pieces we need custom logic
-        match self.field[to.0][to.1].unwrap().piece {
-            Piece::KING => self.make_king_move(from, to),
-            Piece::PAWN => self.make_pawn_move(from, to, promotion_ch),
-            Piece::QUEEN => self.previous_move.insert(0, 'Q'),
-            Piece::ROOK => self.make_rook_move(from),
-            Piece::BISHOP => self.previous_move.insert(0, 'B'),
-            Piece::KNIGHT => self.previous_move.insert(0, 'N'),
+        match moving_piece.piece {
+            Piece::KING => self.make_king_move(castling.map_or(to, |castling| castling.king_to)),
+            Piece::PAWN => self.make_pawn_move(chess_move),
+            _ => (),
         }
 
-        // change turn and add check to notation if necessary
+        // a rook leaving or being captured on its original square loses the castling rights
+        self.remove_castling_rights(from);
+        self.remove_castling_rights(to);
+
+        // change turn
         match self.next_to_move {
             Color::BLACK => {
                 self.next_to_move = Color::WHITE;
-                if can_be_captured_by(Color::BLACK, self.king_position.white_king_position, &self)
-                    .len()
-                    > 0
-                {
-                    self.previous_move.push('+');
-                }
             }
             Color::WHITE => {
                 self.next_to_move = Color::BLACK;
-                if can_be_captured_by(Color::WHITE, self.king_position.black_king_position, &self)
-                    .len()
-                    > 0
-                {
-                    self.previous_move.push('+');
-                }
                 self.turn_number += 1;
             }
         }
 
-        if is_mate(self) {
-            match self.next_to_move {
-                Color::WHITE => {
-                    self.game_result = Some(GameResult::BlackWon);
-                }
-                Color::BLACK => {
-                    self.game_result = Some(GameResult::WhiteWon);
-                }
-            }
-        }
+        self.update_hash(&undo);
+        self.get_rules().after_move(self, &mut undo);
+        undo
     }
-    fn make_rook_move(&mut self, from: (usize, usize)) {
-        self.previous_move.insert(0, 'R');
+    /// Takes back a move made with `apply_move`. Notation and the game result are not part of
+    /// the undo record, so this is not meant to revert `make_move`.
+    pub fn unmake_move(&mut self, undo: &MoveUndo) {
+        let Move { from, to, .. } = undo.chess_move;
 
-        // Take away castling rights if necessary
-        if self.next_to_move == Color::BLACK {
-            if from.0 == 0 && from.1 == 0 {
-                self.can_castle.black_can_long_castle = false;
+        // the removed pieces go back first, since the capturing piece may be one of them
+        for &(square, chess_piece) in undo.removed_pieces.iter() {
+            self.field[square.row][square.col] = Some(chess_piece);
+        }
+
+        match undo.castling {
+            Some(castling) => {
+                let rook = self.piece_at(castling.rook_to);
+                self.field[castling.king_to.row][castling.king_to.col] = None;
+                self.field[castling.rook_to.row][castling.rook_to.col] = None;
+                self.field[from.row][from.col] = Some(undo.moved_piece);
+                self.field[castling.rook_from.row][castling.rook_from.col] = rook;
             }
-            if from.0 == 0 && from.1 == 7 {
-                self.can_castle.black_can_short_castle = false;
+            None => {
+                // putting the moving piece back also reverts promotions
+                self.field[from.row][from.col] = Some(undo.moved_piece);
+                self.field[to.row][to.col] = None;
+                self.field[undo.captured_square.row][undo.captured_square.col] =
+                    undo.captured_piece;
             }
         }
-        if self.next_to_move == Color::WHITE {
-            if from.0 == 7 && from.1 == 0 {
-                self.can_castle.white_can_long_castle = false;
-            }
-            if from.0 == 7 && from.1 == 7 {
-                self.can_castle.white_can_short_castle = false;
+
+        self.can_castle = undo.can_castle;
+        self.en_passant_square = undo.en_passant_square;
+        self.king_position = undo.king_position;
+        self.checks_given = undo.checks_given;
+        self.hash = undo.hash;
+        self.next_to_move = undo.moved_piece.color;
+        if undo.moved_piece.color == Color::WHITE {
+            self.turn_number -= 1;
+        }
+    }
+    // adds everything that changed with the move to the hash, the old castling rights and en
+    // passant square have already been removed from it before the move was made
+    fn update_hash(&mut self, undo: &MoveUndo) {
+        let Move { from, to, .. } = undo.chess_move;
+
+        self.hash ^= get_piece_key(undo.moved_piece, from);
+        if let Some(captured_piece) = undo.captured_piece {
+            self.hash ^= get_piece_key(captured_piece, undo.captured_square);
+        }
+
+        match undo.castling {
+            Some(castling) => {
+                let rook = self.piece_at(castling.rook_to).unwrap();
+                self.hash ^= get_piece_key(undo.moved_piece, castling.king_to)
+                    ^ get_piece_key(rook, castling.rook_from)
+                    ^ get_piece_key(rook, castling.rook_to);
             }
+            // the piece on the target square differs from the moved one for promotions
+            None => self.hash ^= get_piece_key(self.piece_at(to).unwrap(), to),
         }
+
+        self.hash ^= get_side_key() ^ get_castling_key(self) ^ get_en_passant_key(self);
     }
-    fn make_king_move(&mut self, from: (usize, usize), to: (usize, usize)) {
-        self.previous_move.insert(0, 'K');
+    fn get_move_notation(&mut self, chess_move: Move) -> String {
+        let Move { from, to, .. } = chess_move;
+        let moving_piece = self.piece_at(from).unwrap().piece;
+        let mut notation = match moving_piece {
+            Piece::KING => "K",
+            Piece::QUEEN => "Q",
+            Piece::ROOK => "R",
+            Piece::BISHOP => "B",
+            Piece::KNIGHT => "N",
+            Piece::PAWN => "",
+        }
+        .to_string();
+
         // Check if castling move
-        match (from, to) {
-            ((0, 4), (0, 6)) => {
-                self.field[0][5] = self.field[0][7];
-                self.field[0][7] = None;
-                self.previous_move = "0-0".to_string();
+        if moving_piece == Piece::KING {
+            match self.get_castling(chess_move) {
+                Some(castling) if castling.short => return "0-0".to_string(),
+                Some(_) => return "0-0-0".to_string(),
+                None => (),
             }
-            ((0, 4), (0, 2)) => {
-                self.field[0][3] = self.field[0][0];
-                self.field[0][0] = None;
-                self.previous_move = "0-0-0".to_string();
+        }
+
+        // Tell the piece apart from others of its kind that can move to the same square, by the
+        // file it starts on if that is enough and by the rank otherwise
+        let from_notation = from.to_string();
+        let (file, rank) = from_notation.split_at(1);
+        let others = self.get_other_movers(chess_move);
+        if !others.is_empty() {
+            if others.iter().all(|other| other.col != from.col) {
+                notation.push_str(file);
+            } else if others.iter().all(|other| other.row != from.row) {
+                notation.push_str(rank);
+            } else {
+                notation.push_str(file);
+                notation.push_str(rank);
             }
-            ((7, 4), (7, 6)) => {
-                self.field[7][5] = self.field[7][7];
-                self.field[7][7] = None;
-                self.previous_move = "0-0".to_string();
+        }
+
+        // Add x in case we capture, which for en passant is a diagonal pawn move to an empty
+        // square. Pawns that capture are written with the file they start on
+        let is_capture =
+            self.piece_at(to).is_some() || (moving_piece == Piece::PAWN && from.col != to.col);
+        if is_capture {
+            if moving_piece == Piece::PAWN {
+                notation.push_str(file);
             }
-            ((7, 4), (7, 2)) => {
-                self.field[7][3] = self.field[7][0];
-                self.field[7][0] = None;
-                self.previous_move = "0-0-0".to_string();
+            notation.push('x');
+        }
+
+        // Target square
+        notation.push_str(&to.to_string());
+
+        // Add promotion piece, which is ignored for moves that don't reach the last rank
+        if moving_piece == Piece::PAWN && (to.row == 0 || to.row == 7) {
+            if let Some(promotion_ch) = chess_move.promotion.and_then(get_promotion_char) {
+                notation.push('=');
+                notation.push(promotion_ch);
             }
-            _ => (),
         }
 
+        notation
+    }
+    // the squares of the other pieces besides pawns and the king that could make the move, which
+    // a notation like Nd2 alone wouldn't tell apart
+    fn get_other_movers(&mut self, chess_move: Move) -> Vec<Square> {
+        let Move { from, to, .. } = chess_move;
+        let moving_piece = self.piece_at(from).unwrap();
+        if moving_piece.piece == Piece::PAWN || moving_piece.piece == Piece::KING {
+            return vec![];
+        }
+
+        let mut others = vec![];
+        for row in 0..8 {
+            for col in 0..8 {
+                let square = Square::new(row, col);
+                if square != from
+                    && self.piece_at(square) == Some(moving_piece)
+                    && self.validate_move(Move::new(square, to, None)).is_ok()
+                {
+                    others.push(square);
+                }
+            }
+        }
+
+        others
+    }
+    // takes a piece off the board after the move, keeping the hash and castling rights up to date
+    fn remove_piece(&mut self, square: Square, undo: &mut MoveUndo) {
+        let Some(chess_piece) = self.piece_at(square) else {
+            return;
+        };
+        self.field[square.row][square.col] = None;
+        self.hash ^= get_piece_key(chess_piece, square) ^ get_castling_key(self);
+        self.remove_castling_rights(square);
+        self.hash ^= get_castling_key(self);
+        undo.removed_pieces.push((square, chess_piece));
+    }
+    fn remove_castling_rights(&mut self, square: Square) {
+        let CastlingRookCols { short, long } = self.castling_rook_cols;
+        match (square.row, square.col) {
+            (0, col) if col == long => self.can_castle.black_can_long_castle = false,
+            (0, col) if col == short => self.can_castle.black_can_short_castle = false,
+            (7, col) if col == long => self.can_castle.white_can_long_castle = false,
+            (7, col) if col == short => self.can_castle.white_can_short_castle = false,
+            _ => (),
+        }
+    }
+    fn make_king_move(&mut self, to: Square) {
         // Change king position and castling rights
         match self.next_to_move {
             Color::BLACK => {
@@ -230,30 +573,69 @@ impl Game {
             }
         }
     }
-    fn make_pawn_move(&mut self, from: (usize, usize), to: (usize, usize), promotion_ch: char) {
+    fn make_pawn_move(&mut self, chess_move: Move) {
+        let Move { from, to, .. } = chess_move;
+
         // Check if promotion move
-        if to.0 == 7 || to.0 == 0 {
+        if to.row == 7 || to.row == 0 {
             // unwrap due ot already being checked in validation function
-            let promotion_piece = get_promotion_piece(promotion_ch).unwrap();
-            self.field[to.0][to.1] = Some(ChessPiece {
-                piece: promotion_piece,
+            self.field[to.row][to.col] = Some(ChessPiece {
+                piece: chess_move.promotion.unwrap(),
                 color: self.next_to_move,
             });
-            self.previous_move.push('=');
-            self.previous_move.push(promotion_ch);
         }
 
-        // Set en passant rights if pawn moved 2 squares
-        if (from.0 as i32 - to.0 as i32).abs() == 2 {
-            self.can_en_passant = true;
+        // Set en passant square if pawn moved 2 squares, which pawns that start on the first rank
+        // in Horde can't be taken en passant after
+        if (from.row as i32 - to.row as i32).abs() == 2 && (from.row == 1 || from.row == 6) {
+            self.en_passant_square = Some(Square::new((from.row + to.row) / 2, from.col));
         }
     }
 }
 
-fn create_new_game() -> Game {
-    Game {
+const BACK_RANK: [Piece; 8] = [
+    Piece::ROOK,
+    Piece::KNIGHT,
+    Piece::BISHOP,
+    Piece::QUEEN,
+    Piece::KING,
+    Piece::BISHOP,
+    Piece::KNIGHT,
+    Piece::ROOK,
+];
+
+// the pieces of both sides mirror each other, the rooks around the king are the ones that castle
+fn create_new_game(back_rank: [Piece; 8]) -> Game {
+    let mut field: Field = [[None; 8]; 8];
+    for (col, piece) in back_rank.into_iter().enumerate() {
+        field[0][col] = Some(ChessPiece {
+            piece,
+            color: Color::BLACK,
+        });
+        field[1][col] = Some(ChessPiece {
+            piece: Piece::PAWN,
+            color: Color::BLACK,
+        });
+        field[6][col] = Some(ChessPiece {
+            piece: Piece::PAWN,
+            color: Color::WHITE,
+        });
+        field[7][col] = Some(ChessPiece {
+            piece,
+            color: Color::WHITE,
+        });
+    }
+    let king_col = back_rank
+        .iter()
+        .position(|&piece| piece == Piece::KING)
+        .unwrap();
+    let is_rook = |&col: &usize| back_rank[col] == Piece::ROOK;
+
+    let mut game = Game {
         id: Uuid::new_v4(),
+        variant: Variant::Standard,
         game_result: None,
+        draw_offer: None,
         turn_number: 0,
         previous_move: "".to_string(),
         next_to_move: Color::WHITE,
@@ -263,154 +645,185 @@ fn create_new_game() -> Game {
             black_can_short_castle: true,
             black_can_long_castle: true,
         },
-        can_en_passant: false,
+        castling_rook_cols: CastlingRookCols {
+            short: (king_col + 1..8).find(is_rook).unwrap(),
+            long: (0..king_col).find(is_rook).unwrap(),
+        },
+        en_passant_square: None,
         king_position: {
             KingPosition {
-                white_king_position: (7, 4),
-                black_king_position: (0, 4),
+                white_king_position: Square::new(7, king_col),
+                black_king_position: Square::new(0, king_col),
             }
         },
-        field: vec![
-            vec![
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::ROOK,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::KNIGHT,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::BISHOP,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::QUEEN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::KING,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::BISHOP,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::KNIGHT,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::ROOK,
-                }),
-            ],
-            vec![
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::BLACK,
-                    piece: Piece::PAWN,
-                }),
-            ],
-            vec![None; 8],
-            vec![None; 8],
-            vec![None; 8],
-            vec![None; 8],
-            vec![
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::PAWN,
-                }),
-            ],
-            vec![
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::ROOK,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::KNIGHT,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::BISHOP,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::QUEEN,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::KING,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::BISHOP,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::KNIGHT,
-                }),
-                Some(ChessPiece {
-                    color: Color::WHITE,
-                    piece: Piece::ROOK,
-                }),
-            ],
-        ],
+        checks_given: ChecksGiven::default(),
+        field,
+        hash: 0,
+    };
+    game.hash = get_hash(&game);
+
+    game
+}
+
+#[cfg(test)]
+mod test_unmake {
+    use crate::game::{
+        chess_move::{Move, Square},
+        chess_piece::{Color, Piece},
+        fen::{game_from_fen, game_from_variant_fen},
+        variant::Variant,
+        ChessPiece, Game,
+    };
+
+    fn assert_same_position(game: &Game, other: &Game) {
+        assert_eq!(game.field, other.field);
+        assert_eq!(game.next_to_move, other.next_to_move);
+        assert_eq!(game.turn_number, other.turn_number);
+        assert_eq!(game.can_castle, other.can_castle);
+        assert_eq!(game.castling_rook_cols, other.castling_rook_cols);
+        assert_eq!(game.en_passant_square, other.en_passant_square);
+        assert_eq!(game.king_position, other.king_position);
+        assert_eq!(game.checks_given, other.checks_given);
+        assert_eq!(game.hash, other.hash);
+    }
+
+    // validates every move in the position and checks that the game looks exactly the same after
+    // making and unmaking each of them
+    fn assert_all_moves_unmake(game: &mut Game) {
+        let before = game.clone();
+        for from_row in 0..8 {
+            for from_col in 0..8 {
+                for to_row in 0..8 {
+                    for to_col in 0..8 {
+                        let chess_move = Move::new(
+                            Square::new(from_row, from_col),
+                            Square::new(to_row, to_col),
+                            Some(Piece::QUEEN),
+                        );
+                        if game.validate_move(chess_move).is_err() {
+                            assert_same_position(game, &before);
+                            continue;
+                        }
+                        let undo = game.apply_move(chess_move);
+                        game.unmake_move(&undo);
+                        assert_same_position(game, &before);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unmake_castle_move() {
+        let mut game = Game::new();
+        game.field[7][5] = None;
+        game.field[7][6] = None;
+        let before = game.clone();
+
+        let undo = game.apply_move(Move::from_uci("e1g1").unwrap());
+        assert_eq!(game.field[7][5].unwrap().piece, Piece::ROOK);
+        game.unmake_move(&undo);
+
+        assert_same_position(&game, &before);
+    }
+
+    #[test]
+    fn test_unmake_chess960_castle_move() {
+        // the king jumps over its rook to c1 and the rook lands on d1
+        let mut game = game_from_fen("1k6/8/8/8/8/8/8/5RK1 w F - 0 1").unwrap();
+        let before = game.clone();
+
+        let undo = game.apply_move(Move::from_uci("g1f1").unwrap());
+        assert_eq!(game.field[7][2].unwrap().piece, Piece::KING);
+        assert_eq!(game.field[7][3].unwrap().piece, Piece::ROOK);
+        assert!(game.field[7][5].is_none() && game.field[7][6].is_none());
+        game.unmake_move(&undo);
+        assert_same_position(&game, &before);
+
+        assert_all_moves_unmake(&mut game);
+    }
+
+    #[test]
+    fn test_unmake_atomic_explosion() {
+        let mut game = game_from_variant_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            Variant::Atomic,
+        )
+        .unwrap();
+        assert_all_moves_unmake(&mut game);
+    }
+
+    #[test]
+    fn test_unmake_en_passant() {
+        let mut game = Game::new();
+        game.validate_and_make_move(Move::from_uci("e2e4").unwrap())
+            .unwrap();
+        game.validate_and_make_move(Move::from_uci("a7a6").unwrap())
+            .unwrap();
+        game.validate_and_make_move(Move::from_uci("e4e5").unwrap())
+            .unwrap();
+        game.validate_and_make_move(Move::from_uci("d7d5").unwrap())
+            .unwrap();
+        let before = game.clone();
+        assert_eq!(
+            game.get_captured_piece(Move::from_uci("e5d6").unwrap()),
+            Some(Piece::PAWN)
+        );
+        assert_eq!(
+            game.get_captured_piece(Move::from_uci("e5e6").unwrap()),
+            None
+        );
+
+        let undo = game.apply_move(Move::from_uci("e5d6").unwrap());
+        assert_eq!(game.field[3][3], None);
+        assert_eq!(
+            undo.captured_piece,
+            Some(ChessPiece {
+                piece: Piece::PAWN,
+                color: Color::BLACK
+            })
+        );
+        game.unmake_move(&undo);
+
+        assert_same_position(&game, &before);
+    }
+
+    #[test]
+    fn test_unmake_promotion_with_capture() {
+        let mut game = Game::new();
+        game.field[1][0] = Some(ChessPiece {
+            piece: Piece::PAWN,
+            color: Color::WHITE,
+        });
+        let before = game.clone();
+        assert_eq!(
+            game.get_captured_piece(Move::from_uci("a7b8n").unwrap()),
+            Some(Piece::KNIGHT)
+        );
+
+        let undo = game.apply_move(Move::from_uci("a7b8n").unwrap());
+        assert_eq!(
+            game.field[0][1],
+            Some(ChessPiece {
+                piece: Piece::KNIGHT,
+                color: Color::WHITE
+            })
+        );
+        game.unmake_move(&undo);
+
+        assert_same_position(&game, &before);
+    }
+
+    #[test]
+    fn test_validation_leaves_game_untouched() {
+        let mut game = Game::new();
+        assert_all_moves_unmake(&mut game);
+        for uci in [
+            "e2e4", "d7d5", "e4e5", "f7f5", "e5f6", "g7f6", "g1f3", "g8h6", "f1e2", "c8g4",
+        ] {
+            game.validate_and_make_move(Move::from_uci(uci).unwrap())
+                .unwrap();
+            assert_all_moves_unmake(&mut game);
+        }
     }
 }
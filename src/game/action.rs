@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{chess_piece::Color, Game, GameResult},
+    utils::error::{DRAW_ALREADY_OFFERED_ERROR, GAME_FINISHED_ERROR, NO_DRAW_OFFER_ERROR},
+};
+
+/// What a side can do on its turn besides moving.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GameAction {
+    /// the side keeps its turn, and the offer stands until the opponent moves
+    OfferDraw,
+    AcceptDraw,
+    Resign,
+}
+
+impl GameAction {
+    /// The name of the action in votes and tallies, next to the notation of the moves.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameAction::OfferDraw => "OFFER_DRAW",
+            GameAction::AcceptDraw => "ACCEPT_DRAW",
+            GameAction::Resign => "RESIGN",
+        }
+    }
+}
+
+impl Game {
+    pub fn validate_action(&self, action: GameAction) -> Result<(), &'static str> {
+        if self.game_result.is_some() {
+            return Err(GAME_FINISHED_ERROR);
+        }
+
+        match action {
+            GameAction::OfferDraw if self.draw_offer.is_some() => Err(DRAW_ALREADY_OFFERED_ERROR),
+            // only the opponent's offer can be accepted
+            GameAction::AcceptDraw if !matches!(self.draw_offer, Some(color) if color != self.next_to_move) => {
+                Err(NO_DRAW_OFFER_ERROR)
+            }
+            _ => Ok(()),
+        }
+    }
+    /// Takes the action for the side to move. Accepting a draw or resigning ends the game.
+    pub fn make_action(&mut self, action: GameAction) {
+        match action {
+            GameAction::OfferDraw => self.draw_offer = Some(self.next_to_move),
+            GameAction::AcceptDraw => self.game_result = Some(GameResult::Draw),
+            GameAction::Resign => {
+                self.game_result = match self.next_to_move {
+                    Color::WHITE => Some(GameResult::BlackWon),
+                    Color::BLACK => Some(GameResult::WhiteWon),
+                }
+            }
+        }
+    }
+    pub fn validate_and_make_action(&mut self, action: GameAction) -> Result<(), &'static str> {
+        self.validate_action(action)?;
+        self.make_action(action);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_action {
+    use crate::game::{action::GameAction, chess_move::Move, chess_piece::Color, Game, GameResult};
+
+    fn play(game: &mut Game, uci: &str) {
+        game.validate_and_make_move(Move::from_uci(uci).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_accept_draw() {
+        let mut game = Game::new();
+        assert!(game.validate_action(GameAction::AcceptDraw).is_err());
+
+        game.validate_and_make_action(GameAction::OfferDraw)
+            .unwrap();
+        assert_eq!(game.draw_offer, Some(Color::WHITE));
+        // white can't accept its own offer, and still has to move
+        assert!(game.validate_action(GameAction::AcceptDraw).is_err());
+        assert!(game.validate_action(GameAction::OfferDraw).is_err());
+        play(&mut game, "e2e4");
+        assert_eq!(game.draw_offer, Some(Color::WHITE));
+
+        game.validate_and_make_action(GameAction::AcceptDraw)
+            .unwrap();
+        assert_eq!(game.game_result, Some(GameResult::Draw));
+        assert!(game.validate_action(GameAction::Resign).is_err());
+    }
+
+    #[test]
+    fn test_declined_draw() {
+        let mut game = Game::new();
+        play(&mut game, "e2e4");
+        game.validate_and_make_action(GameAction::OfferDraw)
+            .unwrap();
+        play(&mut game, "e7e5");
+        // white answered with a move, so the offer is gone
+        play(&mut game, "g1f3");
+        assert_eq!(game.draw_offer, None);
+        assert!(game.validate_action(GameAction::AcceptDraw).is_err());
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut game = Game::new();
+        play(&mut game, "e2e4");
+        game.validate_and_make_action(GameAction::Resign).unwrap();
+        assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+    }
+}
@@ -0,0 +1,197 @@
+use rand::Rng;
+
+use crate::{
+    game::{chess_piece::Piece, create_new_game, variant::Variant, Game},
+    utils::error::INVALID_CHESS960_POSITION_ERROR,
+};
+
+pub const CHESS960_POSITIONS: u16 = 960;
+/// The number of the standard setup.
+pub const STANDARD_POSITION: u16 = 518;
+
+// the ways to place two knights on the five squares that are left after the bishops and the
+// queen, as indices of those squares
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// Returns the back rank of a Chess960 start position by its number from 0 to 959, as they are
+/// numbered by Scharnagl. The bishops stand on squares of different colors and the king stands
+/// between the rooks.
+pub fn get_back_rank(position: u16) -> Result<[Piece; 8], &'static str> {
+    if position >= CHESS960_POSITIONS {
+        return Err(INVALID_CHESS960_POSITION_ERROR);
+    }
+    let mut back_rank: [Option<Piece>; 8] = [None; 8];
+    let mut position = position as usize;
+
+    // the light squared bishop goes on b, d, f or h and the dark squared one on a, c, e or g
+    back_rank[position % 4 * 2 + 1] = Some(Piece::BISHOP);
+    position /= 4;
+    back_rank[position % 4 * 2] = Some(Piece::BISHOP);
+    position /= 4;
+
+    let empty_cols = |back_rank: &[Option<Piece>; 8]| -> Vec<usize> {
+        (0..8).filter(|&col| back_rank[col].is_none()).collect()
+    };
+    back_rank[empty_cols(&back_rank)[position % 6]] = Some(Piece::QUEEN);
+    position /= 6;
+
+    let cols = empty_cols(&back_rank);
+    let (first_knight, second_knight) = KNIGHT_PLACEMENTS[position];
+    back_rank[cols[first_knight]] = Some(Piece::KNIGHT);
+    back_rank[cols[second_knight]] = Some(Piece::KNIGHT);
+
+    // rook, king and rook fill the last three squares in that order
+    for (col, piece) in
+        empty_cols(&back_rank)
+            .into_iter()
+            .zip([Piece::ROOK, Piece::KING, Piece::ROOK])
+    {
+        back_rank[col] = Some(piece);
+    }
+
+    Ok(back_rank.map(|piece| piece.unwrap()))
+}
+
+pub fn get_random_position() -> u16 {
+    rand::thread_rng().gen_range(0..CHESS960_POSITIONS)
+}
+
+impl Game {
+    /// Starts a Chess960 game from the position with the given number.
+    pub fn new_chess960(position: u16) -> Result<Game, &'static str> {
+        let mut game = create_new_game(get_back_rank(position)?);
+        game.variant = Variant::Chess960;
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod test_chess960 {
+    use crate::{
+        game::{
+            chess960::{get_back_rank, STANDARD_POSITION},
+            chess_move::Move,
+            chess_piece::Piece,
+            fen::{game_from_fen, get_fen},
+            perft::perft,
+            Game,
+        },
+        utils::convert_notation::get_piece_char,
+    };
+
+    fn get_letters(back_rank: [Piece; 8]) -> String {
+        back_rank
+            .iter()
+            .map(|&piece| get_piece_char(piece))
+            .collect()
+    }
+
+    #[test]
+    fn test_back_rank() {
+        assert_eq!(get_letters(get_back_rank(0).unwrap()), "BBQNNRKR");
+        assert_eq!(
+            get_letters(get_back_rank(STANDARD_POSITION).unwrap()),
+            "RNBQKBNR"
+        );
+        assert_eq!(get_letters(get_back_rank(959).unwrap()), "RKRNNQBB");
+        assert!(get_back_rank(960).is_err());
+
+        let mut back_ranks: Vec<String> = (0..960)
+            .map(|position| get_letters(get_back_rank(position).unwrap()))
+            .collect();
+        for back_rank in back_ranks.iter() {
+            let bishops: Vec<usize> = back_rank.match_indices('B').map(|(col, _)| col).collect();
+            assert_ne!(bishops[0] % 2, bishops[1] % 2);
+            let king = back_rank.find('K').unwrap();
+            assert!(back_rank.find('R').unwrap() < king && king < back_rank.rfind('R').unwrap());
+        }
+        back_ranks.sort();
+        back_ranks.dedup();
+        assert_eq!(back_ranks.len(), 960);
+    }
+
+    #[test]
+    fn test_new_game() {
+        assert_eq!(
+            get_fen(&Game::new_chess960(STANDARD_POSITION).unwrap()),
+            get_fen(&Game::new())
+        );
+        // the rooks stand on the outside, so plain KQkq is enough
+        let game = Game::new_chess960(0).unwrap();
+        assert_eq!(
+            get_fen(&game),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_castling() {
+        // the king on g1 stays where it is and the rook on h1 jumps over it to f1
+        let mut game = game_from_fen("6k1/8/8/8/8/8/8/6KR w K - 0 1").unwrap();
+        game.validate_and_make_move(Move::from_uci("g1h1").unwrap())
+            .unwrap();
+        assert_eq!(game.previous_move, "0-0");
+        assert_eq!(get_fen(&game), "6k1/8/8/8/8/8/8/5RK1 b - - 0 1");
+        // the other rook is in the way of the rook
+        let mut game =
+            game_from_fen("bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1").unwrap();
+        assert!(game.validate_move(Move::from_uci("g1h1").unwrap()).is_err());
+
+        // the king takes its rook, moving two squares to c1 would be the wrong notation
+        let mut game = game_from_fen("1r2k3/8/8/8/8/8/8/1R2K3 w Bb - 0 1").unwrap();
+        assert!(game.validate_move(Move::from_uci("e1c1").unwrap()).is_err());
+        assert_eq!(
+            game.get_captured_piece(Move::from_uci("e1b1").unwrap()),
+            None
+        );
+        game.validate_and_make_move(Move::from_uci("e1b1").unwrap())
+            .unwrap();
+        assert_eq!(game.previous_move, "0-0-0");
+        assert_eq!(get_fen(&game), "1r2k3/8/8/8/8/8/8/2KR4 b q - 0 1");
+
+        // the king would pass through the attack of the rook on d8
+        let mut game = game_from_fen("3r1k2/8/8/8/8/8/8/R4K2 w A - 0 1").unwrap();
+        assert!(game.validate_move(Move::from_uci("f1a1").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_perft() {
+        for (fen, expected_nodes) in [
+            (
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+                [21, 528, 12189, 326672],
+            ),
+            (
+                "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+                [21, 807, 18002, 667366],
+            ),
+            (
+                "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+                [20, 479, 10471, 273318],
+            ),
+        ] {
+            let mut game = game_from_fen(fen).expect("Expected FEN to be valid");
+            for (depth, expected) in expected_nodes.into_iter().enumerate() {
+                assert_eq!(
+                    perft(&mut game, depth as u32 + 1),
+                    expected,
+                    "{} at depth {}",
+                    fen,
+                    depth + 1
+                );
+            }
+        }
+    }
+}
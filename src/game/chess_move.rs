@@ -0,0 +1,154 @@
+use std::{fmt, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::{
+    game::chess_piece::Piece,
+    utils::{
+        convert_notation::{
+            get_notation_from_square, get_promotion_char, get_promotion_piece,
+            get_square_from_notation,
+        },
+        error::{INVALID_UCI_MOVE_ERROR, PROMOTION_ERROR},
+    },
+};
+
+/// A square on the board in the same `[row][col]` layout as `Field`, so row 0 is the 8th rank
+/// and col 0 is the a-file.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(try_from = "String")]
+pub struct Square {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Square {
+    pub const fn new(row: usize, col: usize) -> Square {
+        Square { row, col }
+    }
+}
+
+impl FromStr for Square {
+    type Err = &'static str;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        get_square_from_notation(notation)
+    }
+}
+
+impl TryFrom<String> for Square {
+    type Error = &'static str;
+
+    fn try_from(notation: String) -> Result<Self, Self::Error> {
+        notation.parse()
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", get_notation_from_square(*self))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<Piece>,
+}
+
+impl Move {
+    pub const fn new(from: Square, to: Square, promotion: Option<Piece>) -> Move {
+        Move {
+            from,
+            to,
+            promotion,
+        }
+    }
+    /// Parses a move in UCI notation like `e2e4` or `e7e8q`.
+    pub fn from_uci(uci: &str) -> Result<Move, &'static str> {
+        if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+            return Err(INVALID_UCI_MOVE_ERROR);
+        }
+
+        let from = uci[0..2].parse()?;
+        let to = uci[2..4].parse()?;
+        let promotion = match uci[4..].chars().next() {
+            None => None,
+            Some(promotion_ch) => Some(
+                get_promotion_piece(promotion_ch.to_ascii_uppercase()).ok_or(PROMOTION_ERROR)?,
+            ),
+        };
+
+        Ok(Move::new(from, to, promotion))
+    }
+    pub fn to_uci(&self) -> String {
+        let mut uci = format!("{}{}", self.from, self.to);
+        if let Some(promotion_ch) = self.promotion.and_then(get_promotion_char) {
+            uci.push(promotion_ch.to_ascii_lowercase());
+        }
+
+        uci
+    }
+}
+
+impl FromStr for Move {
+    type Err = &'static str;
+
+    fn from_str(uci: &str) -> Result<Self, Self::Err> {
+        Move::from_uci(uci)
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_uci())
+    }
+}
+
+#[cfg(test)]
+mod test_chess_move {
+    use crate::game::{
+        chess_move::{Move, Square},
+        chess_piece::Piece,
+    };
+
+    #[test]
+    fn test_square_notation() {
+        assert_eq!("a8".parse::<Square>(), Ok(Square::new(0, 0)));
+        assert_eq!("e2".parse::<Square>(), Ok(Square::new(6, 4)));
+        assert_eq!(Square::new(7, 7).to_string(), "h1");
+        assert!("i1".parse::<Square>().is_err());
+        assert!("a9".parse::<Square>().is_err());
+        assert!("a".parse::<Square>().is_err());
+    }
+
+    #[test]
+    fn test_move_from_uci() {
+        assert_eq!(
+            Move::from_uci("e2e4"),
+            Ok(Move::new(Square::new(6, 4), Square::new(4, 4), None))
+        );
+        assert_eq!(
+            Move::from_uci("e7e8q"),
+            Ok(Move::new(
+                Square::new(1, 4),
+                Square::new(0, 4),
+                Some(Piece::QUEEN)
+            ))
+        );
+        assert_eq!(
+            Move::from_uci("b2a1N").unwrap().promotion,
+            Some(Piece::KNIGHT)
+        );
+        assert!(Move::from_uci("e7e8k").is_err());
+        assert!(Move::from_uci("e2").is_err());
+        assert!(Move::from_uci("e2e4e5").is_err());
+    }
+
+    #[test]
+    fn test_move_to_uci() {
+        assert_eq!(Move::from_uci("g1f3").unwrap().to_uci(), "g1f3");
+        assert_eq!(Move::from_uci("a2a1R").unwrap().to_uci(), "a2a1r");
+    }
+}
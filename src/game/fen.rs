@@ -0,0 +1,421 @@
+use uuid::Uuid;
+
+use crate::{
+    game::{
+        chess_move::Square,
+        chess_piece::{ChessPiece, Color, Piece},
+        variant::Variant,
+        zobrist::get_hash,
+        CastlingRights, CastlingRookCols, ChecksGiven, Field, Game, KingPosition,
+    },
+    utils::error::INVALID_FEN_ERROR,
+};
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Creates a game from a FEN string. The halfmove clock is not tracked by `Game`, so it is only
+/// checked for being there. Chess960 positions can be given as X-FEN, where KQkq stand for the
+/// outermost rooks and files like B for the others, or as Shredder-FEN with only files like HAha.
+pub fn game_from_fen(fen: &str) -> Result<Game, &'static str> {
+    game_from_variant_fen(fen, Variant::Standard)
+}
+
+/// Creates a game of the variant from a FEN string, which is not part of the FEN itself. Sides
+/// without a king in the variant, like white in Horde, must not have one on the board.
+pub fn game_from_variant_fen(fen: &str, variant: Variant) -> Result<Game, &'static str> {
+    let mut parts = fen.split_whitespace();
+    let placement = parts.next().ok_or(INVALID_FEN_ERROR)?;
+    let side_to_move = parts.next().ok_or(INVALID_FEN_ERROR)?;
+    let castling = parts.next().ok_or(INVALID_FEN_ERROR)?;
+    let en_passant = parts.next().ok_or(INVALID_FEN_ERROR)?;
+    let fullmove_number = match (parts.next(), parts.next()) {
+        (Some(_), Some(fullmove)) => fullmove.parse::<u32>().map_err(|_| INVALID_FEN_ERROR)?,
+        _ => 1,
+    };
+
+    let field = get_field_from_placement(placement)?;
+    let rules = variant.get_rules();
+    let king_position = KingPosition {
+        white_king_position: find_king(&field, Color::WHITE, rules.has_king(Color::WHITE))?,
+        black_king_position: find_king(&field, Color::BLACK, rules.has_king(Color::BLACK))?,
+    };
+
+    let next_to_move = match side_to_move {
+        "w" => Color::WHITE,
+        "b" => Color::BLACK,
+        _ => return Err(INVALID_FEN_ERROR),
+    };
+
+    let (can_castle, castling_rook_cols) = get_castling_rights(castling, &field, king_position)?;
+
+    let en_passant_square = match en_passant {
+        "-" => None,
+        _ => {
+            let square = en_passant
+                .parse::<Square>()
+                .map_err(|_| INVALID_FEN_ERROR)?;
+            match (next_to_move, square.row) {
+                (Color::WHITE, 2) | (Color::BLACK, 5) => Some(square),
+                _ => return Err(INVALID_FEN_ERROR),
+            }
+        }
+    };
+
+    // turn_number counts the moves of white, so it is already increased while black is to move
+    let turn_number = match next_to_move {
+        Color::WHITE => fullmove_number.saturating_sub(1),
+        Color::BLACK => fullmove_number,
+    };
+
+    let mut game = Game {
+        id: Uuid::new_v4(),
+        variant,
+        game_result: None,
+        draw_offer: None,
+        turn_number,
+        next_to_move,
+        previous_move: "".to_string(),
+        can_castle,
+        castling_rook_cols,
+        en_passant_square,
+        king_position,
+        checks_given: ChecksGiven::default(),
+        field,
+        hash: 0,
+    };
+    game.hash = get_hash(&game);
+
+    Ok(game)
+}
+
+/// Describes the position of the game as a FEN string. The halfmove clock is not tracked by
+/// `Game` and is always given as 0.
+pub fn get_fen(game: &Game) -> String {
+    let mut fen = "".to_string();
+
+    for (row, squares) in game.field.iter().enumerate() {
+        let mut empty_squares = 0;
+        for square in squares {
+            match square {
+                None => empty_squares += 1,
+                Some(chess_piece) => {
+                    if empty_squares > 0 {
+                        fen.push_str(&empty_squares.to_string());
+                        empty_squares = 0;
+                    }
+                    fen.push(get_fen_char(*chess_piece));
+                }
+            }
+        }
+        if empty_squares > 0 {
+            fen.push_str(&empty_squares.to_string());
+        }
+        if row < 7 {
+            fen.push('/');
+        }
+    }
+
+    match game.next_to_move {
+        Color::WHITE => fen.push_str(" w "),
+        Color::BLACK => fen.push_str(" b "),
+    }
+
+    let castling_len = fen.len();
+    for (color, can_short_castle, can_long_castle) in [
+        (
+            Color::WHITE,
+            game.can_castle.white_can_short_castle,
+            game.can_castle.white_can_long_castle,
+        ),
+        (
+            Color::BLACK,
+            game.can_castle.black_can_short_castle,
+            game.can_castle.black_can_long_castle,
+        ),
+    ] {
+        if can_short_castle {
+            fen.push(get_castling_char(game, color, true));
+        }
+        if can_long_castle {
+            fen.push(get_castling_char(game, color, false));
+        }
+    }
+    if fen.len() == castling_len {
+        fen.push('-');
+    }
+
+    fen.push(' ');
+    match game.en_passant_square {
+        Some(square) => fen.push_str(&square.to_string()),
+        None => fen.push('-'),
+    }
+
+    let fullmove_number = match game.next_to_move {
+        Color::WHITE => game.turn_number + 1,
+        Color::BLACK => game.turn_number,
+    };
+    fen.push_str(&format!(" 0 {}", fullmove_number));
+
+    fen
+}
+
+fn get_field_from_placement(placement: &str) -> Result<Field, &'static str> {
+    let mut field: Field = [[None; 8]; 8];
+
+    let rows: Vec<&str> = placement.split('/').collect();
+    if rows.len() != 8 {
+        return Err(INVALID_FEN_ERROR);
+    }
+
+    for (row, rank) in rows.iter().enumerate() {
+        let mut col = 0;
+        for ch in rank.chars() {
+            if let Some(empty_squares) = ch.to_digit(10) {
+                col += empty_squares as usize;
+                continue;
+            }
+            if col > 7 {
+                return Err(INVALID_FEN_ERROR);
+            }
+            field[row][col] = Some(get_piece_from_fen_char(ch).ok_or(INVALID_FEN_ERROR)?);
+            col += 1;
+        }
+        if col != 8 {
+            return Err(INVALID_FEN_ERROR);
+        }
+    }
+
+    Ok(field)
+}
+
+// the rights and the files of the castling rooks, which need to be the same for both sides
+fn get_castling_rights(
+    castling: &str,
+    field: &Field,
+    king_position: KingPosition,
+) -> Result<(CastlingRights, CastlingRookCols), &'static str> {
+    let mut can_castle = CastlingRights {
+        white_can_short_castle: false,
+        white_can_long_castle: false,
+        black_can_short_castle: false,
+        black_can_long_castle: false,
+    };
+    let (mut short_rook_col, mut long_rook_col) = (None, None);
+    if castling == "-" {
+        return Ok((can_castle, CastlingRookCols::STANDARD));
+    }
+
+    for ch in castling.chars() {
+        let (color, king) = match ch.is_ascii_uppercase() {
+            true => (Color::WHITE, king_position.white_king_position),
+            false => (Color::BLACK, king_position.black_king_position),
+        };
+        let home_row = match color {
+            Color::WHITE => 7,
+            Color::BLACK => 0,
+        };
+        // a side without a king can't castle either
+        let has_king = field[king.row][king.col]
+            == Some(ChessPiece {
+                piece: Piece::KING,
+                color,
+            });
+        if king.row != home_row || !has_king {
+            return Err(INVALID_FEN_ERROR);
+        }
+        let rook = Some(ChessPiece {
+            piece: Piece::ROOK,
+            color,
+        });
+        let is_rook = |&col: &usize| field[home_row][col] == rook;
+
+        let rook_col = match ch.to_ascii_lowercase() {
+            'k' => (king.col + 1..8).rev().find(is_rook),
+            'q' => (0..king.col).find(is_rook),
+            file @ 'a'..='h' => Some(file as usize - 'a' as usize).filter(is_rook),
+            _ => None,
+        }
+        .ok_or(INVALID_FEN_ERROR)?;
+        let (rook_col_of_side, can_castle_to_side) = match (color, rook_col > king.col) {
+            (Color::WHITE, true) => (&mut short_rook_col, &mut can_castle.white_can_short_castle),
+            (Color::WHITE, false) => (&mut long_rook_col, &mut can_castle.white_can_long_castle),
+            (Color::BLACK, true) => (&mut short_rook_col, &mut can_castle.black_can_short_castle),
+            (Color::BLACK, false) => (&mut long_rook_col, &mut can_castle.black_can_long_castle),
+        };
+        if rook_col_of_side.is_some_and(|col| col != rook_col) {
+            return Err(INVALID_FEN_ERROR);
+        }
+        *rook_col_of_side = Some(rook_col);
+        *can_castle_to_side = true;
+    }
+
+    Ok((
+        can_castle,
+        CastlingRookCols {
+            short: short_rook_col.unwrap_or(CastlingRookCols::STANDARD.short),
+            long: long_rook_col.unwrap_or(CastlingRookCols::STANDARD.long),
+        },
+    ))
+}
+
+// KQkq if the castling rook is the outermost one of its side like in X-FEN, which always holds in
+// standard chess, and the file of the rook otherwise
+fn get_castling_char(game: &Game, color: Color, short: bool) -> char {
+    let home_row = match color {
+        Color::WHITE => 7,
+        Color::BLACK => 0,
+    };
+    let rook = Some(ChessPiece {
+        piece: Piece::ROOK,
+        color,
+    });
+    let CastlingRookCols {
+        short: short_col,
+        long: long_col,
+    } = game.castling_rook_cols;
+    let (rook_col, mut outer_cols, ch) = match short {
+        true => (short_col, short_col + 1..8, 'k'),
+        false => (long_col, 0..long_col, 'q'),
+    };
+    let has_outer_rook = outer_cols.any(|col| game.field[home_row][col] == rook);
+
+    let ch = match has_outer_rook {
+        true => (b'a' + rook_col as u8) as char,
+        false => ch,
+    };
+    match color {
+        Color::WHITE => ch.to_ascii_uppercase(),
+        Color::BLACK => ch,
+    }
+}
+
+// a side without a king gets the square its king would start on
+fn find_king(field: &Field, color: Color, has_king: bool) -> Result<Square, &'static str> {
+    let king = Some(ChessPiece {
+        piece: Piece::KING,
+        color,
+    });
+    let mut king_squares = (0..8)
+        .flat_map(|row| (0..8).map(move |col| Square::new(row, col)))
+        .filter(|square| field[square.row][square.col] == king);
+
+    match (king_squares.next(), king_squares.next(), has_king) {
+        (Some(square), None, true) => Ok(square),
+        (None, None, false) => match color {
+            Color::WHITE => Ok(Square::new(7, 4)),
+            Color::BLACK => Ok(Square::new(0, 4)),
+        },
+        _ => Err(INVALID_FEN_ERROR),
+    }
+}
+
+fn get_piece_from_fen_char(ch: char) -> Option<ChessPiece> {
+    let piece = match ch.to_ascii_lowercase() {
+        'k' => Piece::KING,
+        'q' => Piece::QUEEN,
+        'r' => Piece::ROOK,
+        'b' => Piece::BISHOP,
+        'n' => Piece::KNIGHT,
+        'p' => Piece::PAWN,
+        _ => return None,
+    };
+    let color = if ch.is_ascii_uppercase() {
+        Color::WHITE
+    } else {
+        Color::BLACK
+    };
+
+    Some(ChessPiece { piece, color })
+}
+
+fn get_fen_char(chess_piece: ChessPiece) -> char {
+    let ch = match chess_piece.piece {
+        Piece::KING => 'k',
+        Piece::QUEEN => 'q',
+        Piece::ROOK => 'r',
+        Piece::BISHOP => 'b',
+        Piece::KNIGHT => 'n',
+        Piece::PAWN => 'p',
+    };
+
+    match chess_piece.color {
+        Color::WHITE => ch.to_ascii_uppercase(),
+        Color::BLACK => ch,
+    }
+}
+
+#[cfg(test)]
+mod test_fen {
+    use crate::game::{
+        chess_move::{Move, Square},
+        chess_piece::{Color, Piece},
+        fen::{game_from_fen, get_fen, STARTING_FEN},
+        ChessPiece, Game,
+    };
+
+    #[test]
+    fn test_starting_position() {
+        let game = game_from_fen(STARTING_FEN).expect("Expected starting FEN to be valid");
+
+        assert_eq!(game.field, Game::new().field);
+        assert_eq!(game.turn_number, 0);
+        assert_eq!(get_fen(&Game::new()), STARTING_FEN);
+    }
+
+    #[test]
+    fn test_fen_after_moves() {
+        let mut game = Game::new();
+        game.validate_and_make_move(Move::from_uci("e2e4").unwrap())
+            .unwrap();
+        assert_eq!(
+            get_fen(&game),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+        game.validate_and_make_move(Move::from_uci("g8f6").unwrap())
+            .unwrap();
+        assert_eq!(
+            get_fen(&game),
+            "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w Kq - 0 12";
+        let game = game_from_fen(fen).expect("Expected FEN to be valid");
+
+        assert_eq!(game.king_position.black_king_position, Square::new(0, 4));
+        assert_eq!(
+            game.field[4][4],
+            Some(ChessPiece {
+                piece: Piece::PAWN,
+                color: Color::WHITE,
+            })
+        );
+        assert_eq!(get_fen(&game), fen);
+    }
+
+    #[test]
+    fn test_en_passant_square() {
+        let fen = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3";
+        let mut game = game_from_fen(fen).expect("Expected FEN to be valid");
+
+        assert_eq!(get_fen(&game), fen);
+        game.validate_and_make_move(Move::from_uci("e5f6").unwrap())
+            .expect("Expected en passant to be possible");
+        game.validate_and_make_move(Move::from_uci("e7f6").unwrap())
+            .expect("Expected recapture to be performed");
+    }
+
+    #[test]
+    fn test_invalid_fen() {
+        assert!(game_from_fen("").is_err());
+        assert!(game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1").is_err());
+        assert!(game_from_fen("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_err());
+        assert!(game_from_fen("rnbqqbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_err());
+        assert!(game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1").is_err());
+        assert!(
+            game_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e4 0 1").is_err()
+        );
+    }
+}
@@ -1,241 +1,319 @@
-use crate::game::{Game, GameResult};
+use crate::game::{chess_move::Move, fen::game_from_fen, Game, GameResult};
 
 #[test]
 fn ivanchuk_vs_yusupov() {
     let mut game = Game::new();
 
-    game.validate_and_make_move("c2", "c4", ' ').expect("c4");
+    game.validate_and_make_move(Move::from_uci("c2c4").unwrap())
+        .expect("c4");
     assert_eq!(game.previous_move, "c4");
 
-    game.validate_and_make_move("e7", "e5", ' ').expect("e5");
+    game.validate_and_make_move(Move::from_uci("e7e5").unwrap())
+        .expect("e5");
     assert_eq!(game.previous_move, "e5");
 
-    game.validate_and_make_move("g2", "g3", ' ').expect("g3");
+    game.validate_and_make_move(Move::from_uci("g2g3").unwrap())
+        .expect("g3");
     assert_eq!(game.previous_move, "g3");
 
-    game.validate_and_make_move("d7", "d6", ' ').expect("d6");
+    game.validate_and_make_move(Move::from_uci("d7d6").unwrap())
+        .expect("d6");
     assert_eq!(game.previous_move, "d6");
 
-    game.validate_and_make_move("f1", "g2", ' ').expect("Bg2");
+    game.validate_and_make_move(Move::from_uci("f1g2").unwrap())
+        .expect("Bg2");
     assert_eq!(game.previous_move, "Bg2");
 
-    game.validate_and_make_move("g7", "g6", ' ').expect("g6");
+    game.validate_and_make_move(Move::from_uci("g7g6").unwrap())
+        .expect("g6");
     assert_eq!(game.previous_move, "g6");
 
-    game.validate_and_make_move("d2", "d4", ' ').expect("d4");
+    game.validate_and_make_move(Move::from_uci("d2d4").unwrap())
+        .expect("d4");
     assert_eq!(game.previous_move, "d4");
 
-    game.validate_and_make_move("b8", "d7", ' ').expect("Nd7");
+    game.validate_and_make_move(Move::from_uci("b8d7").unwrap())
+        .expect("Nd7");
     assert_eq!(game.previous_move, "Nd7");
 
-    game.validate_and_make_move("b1", "c3", ' ').expect("Nc3");
+    game.validate_and_make_move(Move::from_uci("b1c3").unwrap())
+        .expect("Nc3");
     assert_eq!(game.previous_move, "Nc3");
 
-    game.validate_and_make_move("f8", "g7", ' ').expect("Bg7");
+    game.validate_and_make_move(Move::from_uci("f8g7").unwrap())
+        .expect("Bg7");
     assert_eq!(game.previous_move, "Bg7");
 
-    game.validate_and_make_move("g1", "f3", ' ').expect("Nf3");
+    game.validate_and_make_move(Move::from_uci("g1f3").unwrap())
+        .expect("Nf3");
     assert_eq!(game.previous_move, "Nf3");
 
-    game.validate_and_make_move("g8", "f6", ' ').expect("Nf6");
-    assert_eq!(game.previous_move, "Nf6");
+    game.validate_and_make_move(Move::from_uci("g8f6").unwrap())
+        .expect("Ngf6");
+    assert_eq!(game.previous_move, "Ngf6");
 
-    game.validate_and_make_move("e1", "g1", ' ').expect("0-0");
+    game.validate_and_make_move(Move::from_uci("e1g1").unwrap())
+        .expect("0-0");
     assert_eq!(game.previous_move, "0-0");
 
-    game.validate_and_make_move("e8", "g8", ' ').expect("0-0");
+    game.validate_and_make_move(Move::from_uci("e8g8").unwrap())
+        .expect("0-0");
     assert_eq!(game.previous_move, "0-0");
 
-    game.validate_and_make_move("d1", "c2", ' ').expect("Qc2");
+    game.validate_and_make_move(Move::from_uci("d1c2").unwrap())
+        .expect("Qc2");
     assert_eq!(game.previous_move, "Qc2");
 
-    game.validate_and_make_move("f8", "e8", ' ').expect("Re8");
+    game.validate_and_make_move(Move::from_uci("f8e8").unwrap())
+        .expect("Re8");
     assert_eq!(game.previous_move, "Re8");
 
-    game.validate_and_make_move("f1", "d1", ' ').expect("Rd1");
+    game.validate_and_make_move(Move::from_uci("f1d1").unwrap())
+        .expect("Rd1");
     assert_eq!(game.previous_move, "Rd1");
 
-    game.validate_and_make_move("c7", "c6", ' ').expect("c6");
+    game.validate_and_make_move(Move::from_uci("c7c6").unwrap())
+        .expect("c6");
     assert_eq!(game.previous_move, "c6");
 
-    game.validate_and_make_move("b2", "b3", ' ').expect("b3");
+    game.validate_and_make_move(Move::from_uci("b2b3").unwrap())
+        .expect("b3");
     assert_eq!(game.previous_move, "b3");
 
-    game.validate_and_make_move("d8", "e7", ' ').expect("Qe7");
+    game.validate_and_make_move(Move::from_uci("d8e7").unwrap())
+        .expect("Qe7");
     assert_eq!(game.previous_move, "Qe7");
 
-    game.validate_and_make_move("c1", "a3", ' ').expect("Ba3");
+    game.validate_and_make_move(Move::from_uci("c1a3").unwrap())
+        .expect("Ba3");
     assert_eq!(game.previous_move, "Ba3");
 
-    game.validate_and_make_move("e5", "e4", ' ').expect("e4");
+    game.validate_and_make_move(Move::from_uci("e5e4").unwrap())
+        .expect("e4");
     assert_eq!(game.previous_move, "e4");
 
-    game.validate_and_make_move("f3", "g5", ' ').expect("Ng5");
+    game.validate_and_make_move(Move::from_uci("f3g5").unwrap())
+        .expect("Ng5");
     assert_eq!(game.previous_move, "Ng5");
 
-    game.validate_and_make_move("e4", "e3", ' ').expect("e3");
+    game.validate_and_make_move(Move::from_uci("e4e3").unwrap())
+        .expect("e3");
     assert_eq!(game.previous_move, "e3");
 
-    game.validate_and_make_move("f2", "f4", ' ').expect("f4");
+    game.validate_and_make_move(Move::from_uci("f2f4").unwrap())
+        .expect("f4");
     assert_eq!(game.previous_move, "f4");
 
-    game.validate_and_make_move("d7", "f8", ' ').expect("Nf8");
+    game.validate_and_make_move(Move::from_uci("d7f8").unwrap())
+        .expect("Nf8");
     assert_eq!(game.previous_move, "Nf8");
 
-    game.validate_and_make_move("b3", "b4", ' ').expect("b4");
+    game.validate_and_make_move(Move::from_uci("b3b4").unwrap())
+        .expect("b4");
     assert_eq!(game.previous_move, "b4");
 
-    game.validate_and_make_move("c8", "f5", ' ').expect("Bf5");
+    game.validate_and_make_move(Move::from_uci("c8f5").unwrap())
+        .expect("Bf5");
     assert_eq!(game.previous_move, "Bf5");
 
-    game.validate_and_make_move("c2", "b3", ' ').expect("Qb3");
+    game.validate_and_make_move(Move::from_uci("c2b3").unwrap())
+        .expect("Qb3");
     assert_eq!(game.previous_move, "Qb3");
 
-    game.validate_and_make_move("h7", "h6", ' ').expect("h6");
+    game.validate_and_make_move(Move::from_uci("h7h6").unwrap())
+        .expect("h6");
     assert_eq!(game.previous_move, "h6");
 
-    game.validate_and_make_move("g5", "f3", ' ').expect("Nf3");
+    game.validate_and_make_move(Move::from_uci("g5f3").unwrap())
+        .expect("Nf3");
     assert_eq!(game.previous_move, "Nf3");
 
-    game.validate_and_make_move("f6", "g4", ' ').expect("Ng4");
+    game.validate_and_make_move(Move::from_uci("f6g4").unwrap())
+        .expect("Ng4");
     assert_eq!(game.previous_move, "Ng4");
 
-    game.validate_and_make_move("b4", "b5", ' ').expect("b5");
+    game.validate_and_make_move(Move::from_uci("b4b5").unwrap())
+        .expect("b5");
     assert_eq!(game.previous_move, "b5");
 
-    game.validate_and_make_move("g6", "g5", ' ').expect("g5");
+    game.validate_and_make_move(Move::from_uci("g6g5").unwrap())
+        .expect("g5");
     assert_eq!(game.previous_move, "g5");
 
-    game.validate_and_make_move("b5", "c6", ' ').expect("xc6");
-    assert_eq!(game.previous_move, "xc6");
+    game.validate_and_make_move(Move::from_uci("b5c6").unwrap())
+        .expect("bxc6");
+    assert_eq!(game.previous_move, "bxc6");
 
-    game.validate_and_make_move("b7", "c6", ' ').expect("xc6");
-    assert_eq!(game.previous_move, "xc6");
+    game.validate_and_make_move(Move::from_uci("b7c6").unwrap())
+        .expect("bxc6");
+    assert_eq!(game.previous_move, "bxc6");
 
-    game.validate_and_make_move("f3", "e5", ' ').expect("Ne5");
+    game.validate_and_make_move(Move::from_uci("f3e5").unwrap())
+        .expect("Ne5");
     assert_eq!(game.previous_move, "Ne5");
 
-    game.validate_and_make_move("g5", "f4", ' ').expect("xf4");
-    assert_eq!(game.previous_move, "xf4");
+    game.validate_and_make_move(Move::from_uci("g5f4").unwrap())
+        .expect("gxf4");
+    assert_eq!(game.previous_move, "gxf4");
 
-    game.validate_and_make_move("e5", "c6", ' ').expect("Nxc6");
+    game.validate_and_make_move(Move::from_uci("e5c6").unwrap())
+        .expect("Nxc6");
     assert_eq!(game.previous_move, "Nxc6");
 
-    game.validate_and_make_move("e7", "g5", ' ').expect("Qg5");
+    game.validate_and_make_move(Move::from_uci("e7g5").unwrap())
+        .expect("Qg5");
     assert_eq!(game.previous_move, "Qg5");
 
-    game.validate_and_make_move("a3", "d6", ' ').expect("Bxd6");
+    game.validate_and_make_move(Move::from_uci("a3d6").unwrap())
+        .expect("Bxd6");
     assert_eq!(game.previous_move, "Bxd6");
 
-    game.validate_and_make_move("f8", "g6", ' ').expect("Ng6");
+    game.validate_and_make_move(Move::from_uci("f8g6").unwrap())
+        .expect("Ng6");
     assert_eq!(game.previous_move, "Ng6");
 
-    game.validate_and_make_move("c3", "d5", ' ').expect("Nd5");
+    game.validate_and_make_move(Move::from_uci("c3d5").unwrap())
+        .expect("Nd5");
     assert_eq!(game.previous_move, "Nd5");
 
-    game.validate_and_make_move("g5", "h5", ' ').expect("Qh5");
+    game.validate_and_make_move(Move::from_uci("g5h5").unwrap())
+        .expect("Qh5");
     assert_eq!(game.previous_move, "Qh5");
 
-    game.validate_and_make_move("h2", "h4", ' ').expect("h4");
+    game.validate_and_make_move(Move::from_uci("h2h4").unwrap())
+        .expect("h4");
     assert_eq!(game.previous_move, "h4");
 
-    game.validate_and_make_move("g6", "h4", ' ').expect("Nxh4");
+    game.validate_and_make_move(Move::from_uci("g6h4").unwrap())
+        .expect("Nxh4");
     assert_eq!(game.previous_move, "Nxh4");
 
-    game.validate_and_make_move("g3", "h4", ' ').expect("xh4");
-    assert_eq!(game.previous_move, "xh4");
+    game.validate_and_make_move(Move::from_uci("g3h4").unwrap())
+        .expect("gxh4");
+    assert_eq!(game.previous_move, "gxh4");
 
-    game.validate_and_make_move("h5", "h4", ' ').expect("Qxh4");
+    game.validate_and_make_move(Move::from_uci("h5h4").unwrap())
+        .expect("Qxh4");
     assert_eq!(game.previous_move, "Qxh4");
 
-    game.validate_and_make_move("d5", "e7", ' ').expect("Ne7+");
-    assert_eq!(game.previous_move, "Ne7+");
+    game.validate_and_make_move(Move::from_uci("d5e7").unwrap())
+        .expect("Nde7+");
+    assert_eq!(game.previous_move, "Nde7+");
 
-    game.validate_and_make_move("g8", "h8", ' ').expect("Kh8");
+    game.validate_and_make_move(Move::from_uci("g8h8").unwrap())
+        .expect("Kh8");
     assert_eq!(game.previous_move, "Kh8");
 
-    game.validate_and_make_move("e7", "f5", ' ').expect("Nxf5");
+    game.validate_and_make_move(Move::from_uci("e7f5").unwrap())
+        .expect("Nxf5");
     assert_eq!(game.previous_move, "Nxf5");
 
-    game.validate_and_make_move("h4", "h2", ' ').expect("Qh2+");
+    game.validate_and_make_move(Move::from_uci("h4h2").unwrap())
+        .expect("Qh2+");
     assert_eq!(game.previous_move, "Qh2+");
 
-    game.validate_and_make_move("g1", "f1", ' ').expect("Kf1");
+    game.validate_and_make_move(Move::from_uci("g1f1").unwrap())
+        .expect("Kf1");
     assert_eq!(game.previous_move, "Kf1");
 
-    game.validate_and_make_move("e8", "e6", ' ').expect("Re6");
+    game.validate_and_make_move(Move::from_uci("e8e6").unwrap())
+        .expect("Re6");
     assert_eq!(game.previous_move, "Re6");
 
-    game.validate_and_make_move("b3", "b7", ' ').expect("Qb7");
+    game.validate_and_make_move(Move::from_uci("b3b7").unwrap())
+        .expect("Qb7");
     assert_eq!(game.previous_move, "Qb7");
 
-    game.validate_and_make_move("e6", "g6", ' ').expect("Rg6");
+    game.validate_and_make_move(Move::from_uci("e6g6").unwrap())
+        .expect("Rg6");
     assert_eq!(game.previous_move, "Rg6");
 
-    game.validate_and_make_move("b7", "a8", ' ').expect("Qxa8+");
+    game.validate_and_make_move(Move::from_uci("b7a8").unwrap())
+        .expect("Qxa8+");
     assert_eq!(game.previous_move, "Qxa8+");
 
-    game.validate_and_make_move("h8", "h7", ' ').expect("Kh7");
+    game.validate_and_make_move(Move::from_uci("h8h7").unwrap())
+        .expect("Kh7");
     assert_eq!(game.previous_move, "Kh7");
 
-    game.validate_and_make_move("a8", "g8", ' ').expect("Qg8+");
+    game.validate_and_make_move(Move::from_uci("a8g8").unwrap())
+        .expect("Qg8+");
     assert_eq!(game.previous_move, "Qg8+");
 
-    game.validate_and_make_move("h7", "g8", ' ').expect("Kxg8");
+    game.validate_and_make_move(Move::from_uci("h7g8").unwrap())
+        .expect("Kxg8");
     assert_eq!(game.previous_move, "Kxg8");
 
-    game.validate_and_make_move("c6", "e7", ' ').expect("Ne7+");
-    assert_eq!(game.previous_move, "Ne7+");
+    game.validate_and_make_move(Move::from_uci("c6e7").unwrap())
+        .expect("Nce7+");
+    assert_eq!(game.previous_move, "Nce7+");
 
-    game.validate_and_make_move("g8", "h7", ' ').expect("Kh7");
+    game.validate_and_make_move(Move::from_uci("g8h7").unwrap())
+        .expect("Kh7");
     assert_eq!(game.previous_move, "Kh7");
 
-    game.validate_and_make_move("e7", "g6", ' ').expect("Nxg6");
+    game.validate_and_make_move(Move::from_uci("e7g6").unwrap())
+        .expect("Nxg6");
     assert_eq!(game.previous_move, "Nxg6");
 
-    game.validate_and_make_move("f7", "g6", ' ').expect("xg6");
-    assert_eq!(game.previous_move, "xg6");
+    game.validate_and_make_move(Move::from_uci("f7g6").unwrap())
+        .expect("fxg6");
+    assert_eq!(game.previous_move, "fxg6");
 
-    game.validate_and_make_move("f5", "g7", ' ').expect("Nxg7");
+    game.validate_and_make_move(Move::from_uci("f5g7").unwrap())
+        .expect("Nxg7");
     assert_eq!(game.previous_move, "Nxg7");
 
-    game.validate_and_make_move("g4", "f2", ' ').expect("Nf2");
+    game.validate_and_make_move(Move::from_uci("g4f2").unwrap())
+        .expect("Nf2");
     assert_eq!(game.previous_move, "Nf2");
 
-    game.validate_and_make_move("d6", "f4", ' ').expect("Bxf4");
+    game.validate_and_make_move(Move::from_uci("d6f4").unwrap())
+        .expect("Bxf4");
     assert_eq!(game.previous_move, "Bxf4");
 
-    game.validate_and_make_move("h2", "f4", ' ').expect("Qxf4");
+    game.validate_and_make_move(Move::from_uci("h2f4").unwrap())
+        .expect("Qxf4");
     assert_eq!(game.previous_move, "Qxf4");
 
-    game.validate_and_make_move("g7", "e6", ' ').expect("Ne6");
+    game.validate_and_make_move(Move::from_uci("g7e6").unwrap())
+        .expect("Ne6");
     assert_eq!(game.previous_move, "Ne6");
 
-    game.validate_and_make_move("f4", "h2", ' ').expect("Qh2");
+    game.validate_and_make_move(Move::from_uci("f4h2").unwrap())
+        .expect("Qh2");
     assert_eq!(game.previous_move, "Qh2");
 
-    game.validate_and_make_move("d1", "b1", ' ').expect("Rb1");
-    assert_eq!(game.previous_move, "Rb1");
+    game.validate_and_make_move(Move::from_uci("d1b1").unwrap())
+        .expect("Rdb1");
+    assert_eq!(game.previous_move, "Rdb1");
 
-    game.validate_and_make_move("f2", "h3", ' ').expect("Nh3");
+    game.validate_and_make_move(Move::from_uci("f2h3").unwrap())
+        .expect("Nh3");
     assert_eq!(game.previous_move, "Nh3");
 
-    game.validate_and_make_move("b1", "b7", ' ').expect("Rb7+");
+    game.validate_and_make_move(Move::from_uci("b1b7").unwrap())
+        .expect("Rb7+");
     assert_eq!(game.previous_move, "Rb7+");
 
-    game.validate_and_make_move("h7", "h8", ' ').expect("Kh8");
+    game.validate_and_make_move(Move::from_uci("h7h8").unwrap())
+        .expect("Kh8");
     assert_eq!(game.previous_move, "Kh8");
 
-    game.validate_and_make_move("b7", "b8", ' ').expect("Rb8+");
+    game.validate_and_make_move(Move::from_uci("b7b8").unwrap())
+        .expect("Rb8+");
     assert_eq!(game.previous_move, "Rb8+");
 
-    game.validate_and_make_move("h2", "b8", ' ').expect("Qxb8");
+    game.validate_and_make_move(Move::from_uci("h2b8").unwrap())
+        .expect("Qxb8");
     assert_eq!(game.previous_move, "Qxb8");
 
-    game.validate_and_make_move("g2", "h3", ' ').expect("Bxh3");
+    game.validate_and_make_move(Move::from_uci("g2h3").unwrap())
+        .expect("Bxh3");
     assert_eq!(game.previous_move, "Bxh3");
 
-    game.validate_and_make_move("b8", "g3", ' ').expect("Qg3");
+    game.validate_and_make_move(Move::from_uci("b8g3").unwrap())
+        .expect("Qg3");
     assert_eq!(game.previous_move, "Qg3");
 }
 
@@ -243,162 +321,278 @@ fn ivanchuk_vs_yusupov() {
 fn hammer_vs_carlsen() {
     let mut game = Game::new();
 
-    game.validate_and_make_move("e2", "e4", ' ').expect("e4");
+    game.validate_and_make_move(Move::from_uci("e2e4").unwrap())
+        .expect("e4");
     assert_eq!(game.previous_move, "e4");
 
-    game.validate_and_make_move("g7", "g6", ' ').expect("g6");
+    game.validate_and_make_move(Move::from_uci("g7g6").unwrap())
+        .expect("g6");
     assert_eq!(game.previous_move, "g6");
 
-    game.validate_and_make_move("d2", "d4", ' ').expect("d4");
+    game.validate_and_make_move(Move::from_uci("d2d4").unwrap())
+        .expect("d4");
     assert_eq!(game.previous_move, "d4");
 
-    game.validate_and_make_move("e7", "e6", ' ').expect("e6");
+    game.validate_and_make_move(Move::from_uci("e7e6").unwrap())
+        .expect("e6");
     assert_eq!(game.previous_move, "e6");
 
-    game.validate_and_make_move("g1", "f3", ' ').expect("Nf3");
+    game.validate_and_make_move(Move::from_uci("g1f3").unwrap())
+        .expect("Nf3");
     assert_eq!(game.previous_move, "Nf3");
 
-    game.validate_and_make_move("g8", "e7", ' ').expect("Ne7");
+    game.validate_and_make_move(Move::from_uci("g8e7").unwrap())
+        .expect("Ne7");
     assert_eq!(game.previous_move, "Ne7");
 
-    game.validate_and_make_move("h2", "h4", ' ').expect("h4");
+    game.validate_and_make_move(Move::from_uci("h2h4").unwrap())
+        .expect("h4");
     assert_eq!(game.previous_move, "h4");
 
-    game.validate_and_make_move("h7", "h6", ' ').expect("h6");
+    game.validate_and_make_move(Move::from_uci("h7h6").unwrap())
+        .expect("h6");
     assert_eq!(game.previous_move, "h6");
 
-    game.validate_and_make_move("c1", "f4", ' ').expect("Bf4");
+    game.validate_and_make_move(Move::from_uci("c1f4").unwrap())
+        .expect("Bf4");
     assert_eq!(game.previous_move, "Bf4");
 
-    game.validate_and_make_move("f8", "g7", ' ').expect("Bg7");
+    game.validate_and_make_move(Move::from_uci("f8g7").unwrap())
+        .expect("Bg7");
     assert_eq!(game.previous_move, "Bg7");
 
-    game.validate_and_make_move("d1", "d2", ' ').expect("Qd2");
+    game.validate_and_make_move(Move::from_uci("d1d2").unwrap())
+        .expect("Qd2");
     assert_eq!(game.previous_move, "Qd2");
 
-    game.validate_and_make_move("d7", "d5", ' ').expect("d5");
+    game.validate_and_make_move(Move::from_uci("d7d5").unwrap())
+        .expect("d5");
     assert_eq!(game.previous_move, "d5");
 
-    game.validate_and_make_move("e4", "e5", ' ').expect("e5");
+    game.validate_and_make_move(Move::from_uci("e4e5").unwrap())
+        .expect("e5");
     assert_eq!(game.previous_move, "e5");
 
-    game.validate_and_make_move("c7", "c5", ' ').expect("c5");
+    game.validate_and_make_move(Move::from_uci("c7c5").unwrap())
+        .expect("c5");
     assert_eq!(game.previous_move, "c5");
 
-    game.validate_and_make_move("c2", "c3", ' ').expect("c3");
+    game.validate_and_make_move(Move::from_uci("c2c3").unwrap())
+        .expect("c3");
     assert_eq!(game.previous_move, "c3");
 
-    game.validate_and_make_move("b8", "c6", ' ').expect("Nc6");
-    assert_eq!(game.previous_move, "Nc6");
+    game.validate_and_make_move(Move::from_uci("b8c6").unwrap())
+        .expect("Nbc6");
+    assert_eq!(game.previous_move, "Nbc6");
 
-    game.validate_and_make_move("b1", "a3", ' ').expect("Na3");
+    game.validate_and_make_move(Move::from_uci("b1a3").unwrap())
+        .expect("Na3");
     assert_eq!(game.previous_move, "Na3");
 
-    game.validate_and_make_move("e8", "f8", ' ').expect("Kf8");
+    game.validate_and_make_move(Move::from_uci("e8f8").unwrap())
+        .expect("Kf8");
     assert_eq!(game.previous_move, "Kf8");
-    assert_eq!(game.can_castle.black_can_long_castle, false);
-    assert_eq!(game.can_castle.white_can_long_castle, true);
+    assert!(!game.can_castle.black_can_long_castle);
+    assert!(game.can_castle.white_can_long_castle);
 
-    game.validate_and_make_move("d4", "c5", ' ').expect("xc5");
-    assert_eq!(game.previous_move, "xc5");
+    game.validate_and_make_move(Move::from_uci("d4c5").unwrap())
+        .expect("dxc5");
+    assert_eq!(game.previous_move, "dxc5");
 
-    game.validate_and_make_move("b7", "b6", ' ').expect("b6");
+    game.validate_and_make_move(Move::from_uci("b7b6").unwrap())
+        .expect("b6");
     assert_eq!(game.previous_move, "b6");
 
-    game.validate_and_make_move("c5", "b6", ' ').expect("xb6");
-    assert_eq!(game.previous_move, "xb6");
+    game.validate_and_make_move(Move::from_uci("c5b6").unwrap())
+        .expect("cxb6");
+    assert_eq!(game.previous_move, "cxb6");
 
-    game.validate_and_make_move("d8", "b6", ' ').expect("Qxb6");
+    game.validate_and_make_move(Move::from_uci("d8b6").unwrap())
+        .expect("Qxb6");
     assert_eq!(game.previous_move, "Qxb6");
 
-    game.validate_and_make_move("f1", "d3", ' ').expect("Bd3");
+    game.validate_and_make_move(Move::from_uci("f1d3").unwrap())
+        .expect("Bd3");
     assert_eq!(game.previous_move, "Bd3");
 
-    game.validate_and_make_move("c8", "a6", ' ').expect("Ba6");
+    game.validate_and_make_move(Move::from_uci("c8a6").unwrap())
+        .expect("Ba6");
     assert_eq!(game.previous_move, "Ba6");
 
-    game.validate_and_make_move("b2", "b4", ' ').expect("b4");
+    game.validate_and_make_move(Move::from_uci("b2b4").unwrap())
+        .expect("b4");
     assert_eq!(game.previous_move, "b4");
 
-    game.validate_and_make_move("d5", "d4", ' ').expect("d4");
+    game.validate_and_make_move(Move::from_uci("d5d4").unwrap())
+        .expect("d4");
     assert_eq!(game.previous_move, "d4");
 
-    game.validate_and_make_move("b4", "b5", ' ').expect("b5");
+    game.validate_and_make_move(Move::from_uci("b4b5").unwrap())
+        .expect("b5");
     assert_eq!(game.previous_move, "b5");
 
-    game.validate_and_make_move("d4", "c3", ' ').expect("xc3");
-    assert_eq!(game.previous_move, "xc3");
+    game.validate_and_make_move(Move::from_uci("d4c3").unwrap())
+        .expect("dxc3");
+    assert_eq!(game.previous_move, "dxc3");
 
-    game.validate_and_make_move("d2", "e3", ' ').expect("Qe3");
+    game.validate_and_make_move(Move::from_uci("d2e3").unwrap())
+        .expect("Qe3");
     assert_eq!(game.previous_move, "Qe3");
 
-    game.validate_and_make_move("e7", "d5", ' ').expect("Nd5");
+    game.validate_and_make_move(Move::from_uci("e7d5").unwrap())
+        .expect("Nd5");
     assert_eq!(game.previous_move, "Nd5");
 
-    game.validate_and_make_move("e3", "b6", ' ').expect("Qxb6");
+    game.validate_and_make_move(Move::from_uci("e3b6").unwrap())
+        .expect("Qxb6");
     assert_eq!(game.previous_move, "Qxb6");
 
-    game.validate_and_make_move("a7", "b6", ' ').expect("xb6");
-    assert_eq!(game.previous_move, "xb6");
+    game.validate_and_make_move(Move::from_uci("a7b6").unwrap())
+        .expect("axb6");
+    assert_eq!(game.previous_move, "axb6");
 
-    game.validate_and_make_move("b5", "a6", ' ').expect("xa6");
-    assert_eq!(game.previous_move, "xa6");
+    game.validate_and_make_move(Move::from_uci("b5a6").unwrap())
+        .expect("bxa6");
+    assert_eq!(game.previous_move, "bxa6");
 
-    game.validate_and_make_move("d5", "f4", ' ').expect("Nxf4");
+    game.validate_and_make_move(Move::from_uci("d5f4").unwrap())
+        .expect("Nxf4");
     assert_eq!(game.previous_move, "Nxf4");
 
-    game.validate_and_make_move("d3", "e4", ' ').expect("Be4");
+    game.validate_and_make_move(Move::from_uci("d3e4").unwrap())
+        .expect("Be4");
     assert_eq!(game.previous_move, "Be4");
 
-    game.validate_and_make_move("a8", "a6", ' ').expect("Rxa6");
+    game.validate_and_make_move(Move::from_uci("a8a6").unwrap())
+        .expect("Rxa6");
     assert_eq!(game.previous_move, "Rxa6");
 
-    game.validate_and_make_move("e4", "c6", ' ').expect("Bxc6");
+    game.validate_and_make_move(Move::from_uci("e4c6").unwrap())
+        .expect("Bxc6");
     assert_eq!(game.previous_move, "Bxc6");
 
-    game.validate_and_make_move("a6", "a3", ' ').expect("Rxa3");
+    game.validate_and_make_move(Move::from_uci("a6a3").unwrap())
+        .expect("Rxa3");
     assert_eq!(game.previous_move, "Rxa3");
 
-    game.validate_and_make_move("e1", "c1", ' ').expect("0-0-0");
+    game.validate_and_make_move(Move::from_uci("e1c1").unwrap())
+        .expect("0-0-0");
     assert_eq!(game.previous_move, "0-0-0");
 
-    game.validate_and_make_move("f8", "g8", ' ').expect("Kg8");
+    game.validate_and_make_move(Move::from_uci("f8g8").unwrap())
+        .expect("Kg8");
     assert_eq!(game.previous_move, "Kg8");
 
-    game.validate_and_make_move("d1", "d8", ' ').expect("Rd8+");
+    game.validate_and_make_move(Move::from_uci("d1d8").unwrap())
+        .expect("Rd8+");
     assert_eq!(game.previous_move, "Rd8+");
 
-    game.validate_and_make_move("g8", "h7", ' ').expect("Kh7");
+    game.validate_and_make_move(Move::from_uci("g8h7").unwrap())
+        .expect("Kh7");
     assert_eq!(game.previous_move, "Kh7");
 
-    game.validate_and_make_move("f3", "g5", ' ').expect("Ng5+");
+    game.validate_and_make_move(Move::from_uci("f3g5").unwrap())
+        .expect("Ng5+");
     assert_eq!(game.previous_move, "Ng5+");
 
-    game.validate_and_make_move("h6", "g5", ' ').expect("xg5");
-    assert_eq!(game.previous_move, "xg5");
+    game.validate_and_make_move(Move::from_uci("h6g5").unwrap())
+        .expect("hxg5");
+    assert_eq!(game.previous_move, "hxg5");
 
-    game.validate_and_make_move("h4", "g5", ' ').expect("xg5+");
-    assert_eq!(game.previous_move, "xg5+");
+    game.validate_and_make_move(Move::from_uci("h4g5").unwrap())
+        .expect("hxg5+");
+    assert_eq!(game.previous_move, "hxg5+");
 
-    game.validate_and_make_move("f4", "h5", ' ').expect("Nh5");
+    game.validate_and_make_move(Move::from_uci("f4h5").unwrap())
+        .expect("Nh5");
     assert_eq!(game.previous_move, "Nh5");
 
-    game.validate_and_make_move("h1", "h5", ' ').expect("Rxh5+");
+    game.validate_and_make_move(Move::from_uci("h1h5").unwrap())
+        .expect("Rxh5+");
     assert_eq!(game.previous_move, "Rxh5+");
 
-    game.validate_and_make_move("g6", "h5", ' ').expect("xh5");
-    assert_eq!(game.previous_move, "xh5");
+    game.validate_and_make_move(Move::from_uci("g6h5").unwrap())
+        .expect("gxh5");
+    assert_eq!(game.previous_move, "gxh5");
 
-    game.validate_and_make_move("c6", "e4", ' ').expect("Be4+");
+    game.validate_and_make_move(Move::from_uci("c6e4").unwrap())
+        .expect("Be4+");
     assert_eq!(game.previous_move, "Be4+");
 
-    game.validate_and_make_move("f7", "f5", ' ').expect("f5");
+    game.validate_and_make_move(Move::from_uci("f7f5").unwrap())
+        .expect("f5");
     assert_eq!(game.previous_move, "f5");
 
     assert_eq!(game.game_result, None);
 
-    game.validate_and_make_move("e5", "f6", ' ').expect("xf6+");
-    assert_eq!(game.previous_move, "xf6+");
+    game.validate_and_make_move(Move::from_uci("e5f6").unwrap())
+        .expect("exf6+");
+    assert_eq!(game.previous_move, "exf6+");
 
     assert_eq!(game.game_result, Some(GameResult::WhiteWon));
 }
+
+#[test]
+fn scholars_mate() {
+    let mut game = Game::new();
+
+    game.validate_and_make_move(Move::from_uci("e2e4").unwrap())
+        .expect("e4");
+    game.validate_and_make_move(Move::from_uci("e7e5").unwrap())
+        .expect("e5");
+    game.validate_and_make_move(Move::from_uci("f1c4").unwrap())
+        .expect("Bc4");
+    game.validate_and_make_move(Move::from_uci("b8c6").unwrap())
+        .expect("Nc6");
+    game.validate_and_make_move(Move::from_uci("d1h5").unwrap())
+        .expect("Qh5");
+    game.validate_and_make_move(Move::from_uci("g8f6").unwrap())
+        .expect("Nf6");
+    assert_eq!(game.game_result, None);
+
+    // the king could only escape to squares its own pieces stand on
+    game.validate_and_make_move(Move::from_uci("h5f7").unwrap())
+        .expect("Qxf7+");
+    assert_eq!(game.previous_move, "Qxf7+");
+    assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+}
+
+#[test]
+fn back_rank_mate() {
+    let mut game = game_from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+
+    game.validate_and_make_move(Move::from_uci("a1a8").unwrap())
+        .expect("Ra8+");
+    assert_eq!(game.previous_move, "Ra8+");
+    assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+}
+
+#[test]
+fn smothered_mate() {
+    let mut game = game_from_fen("6rk/6pp/8/6N1/8/8/8/6K1 w - - 0 1").unwrap();
+
+    game.validate_and_make_move(Move::from_uci("g5f7").unwrap())
+        .expect("Nf7+");
+    assert_eq!(game.previous_move, "Nf7+");
+    assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+}
+
+#[test]
+fn check_that_can_be_blocked() {
+    let mut game = game_from_fen("6k1/5ppp/8/8/8/8/2r2PPP/R5K1 w - - 0 1").unwrap();
+
+    // the rook can block on c8
+    game.validate_and_make_move(Move::from_uci("a1a8").unwrap())
+        .expect("Ra8+");
+    assert_eq!(game.game_result, None);
+}
+
+#[test]
+fn stalemate() {
+    let mut game = game_from_fen("7k/8/8/6Q1/8/8/8/6K1 w - - 0 1").unwrap();
+
+    game.validate_and_make_move(Move::from_uci("g5g6").unwrap())
+        .expect("Qg6");
+    assert_eq!(game.previous_move, "Qg6");
+    assert_eq!(game.game_result, Some(GameResult::Draw));
+}
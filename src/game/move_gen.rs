@@ -0,0 +1,242 @@
+use crate::{
+    game::{
+        chess_move::{Move, Square},
+        chess_piece::{Color, Piece},
+        validation::check_mate::{
+            DIAGONAL_DIRECTIONS, KING_OFFSETS, KNIGHT_OFFSETS, LINEAR_DIRECTIONS,
+        },
+        Game,
+    },
+    utils::{error::INVALID_HISTORY_ERROR, is_in_bounds},
+};
+
+const PROMOTION_PIECES: [Piece; 4] = [Piece::QUEEN, Piece::ROOK, Piece::BISHOP, Piece::KNIGHT];
+
+/// Returns every legal move of the side to move. Candidate squares are collected per piece and
+/// then run through the same validation as moves coming in through the API, so this also tests
+/// the validation.
+pub fn get_legal_moves(game: &mut Game) -> Vec<Move> {
+    let mut legal_moves = vec![];
+    let mut candidates = vec![];
+
+    for row in 0..8 {
+        for col in 0..8 {
+            let chess_piece = match game.field[row][col] {
+                Some(chess_piece) if chess_piece.color == game.next_to_move => chess_piece,
+                _ => continue,
+            };
+
+            let from = Square::new(row, col);
+            candidates.clear();
+            match chess_piece.piece {
+                Piece::PAWN => get_pawn_candidates(from, chess_piece.color, &mut candidates),
+                Piece::KNIGHT => get_step_candidates(from, &KNIGHT_OFFSETS, &mut candidates),
+                Piece::BISHOP => {
+                    get_slide_candidates(from, &DIAGONAL_DIRECTIONS, game, &mut candidates)
+                }
+                Piece::ROOK => {
+                    get_slide_candidates(from, &LINEAR_DIRECTIONS, game, &mut candidates)
+                }
+                Piece::QUEEN => {
+                    get_slide_candidates(from, &DIAGONAL_DIRECTIONS, game, &mut candidates);
+                    get_slide_candidates(from, &LINEAR_DIRECTIONS, game, &mut candidates);
+                }
+                Piece::KING => {
+                    get_step_candidates(from, &KING_OFFSETS, &mut candidates);
+                    get_castling_candidates(from, game, &mut candidates);
+                }
+            }
+
+            for &to in candidates.iter() {
+                let is_promotion = chess_piece.piece == Piece::PAWN && (to.row == 0 || to.row == 7);
+                if !is_promotion {
+                    let chess_move = Move::new(from, to, None);
+                    if game.validate_move(chess_move).is_ok() {
+                        legal_moves.push(chess_move);
+                    }
+                    continue;
+                }
+                for promotion in PROMOTION_PIECES {
+                    let chess_move = Move::new(from, to, Some(promotion));
+                    if game.validate_move(chess_move).is_ok() {
+                        legal_moves.push(chess_move);
+                    }
+                }
+            }
+        }
+    }
+
+    legal_moves
+}
+
+/// Finds the legal move that is written as the given notation, like the notation stored for votes
+/// and in the game history, e.g. `Nxf5`, `0-0` or `e8=Q+`.
+pub fn find_move_by_notation(game: &mut Game, notation: &str) -> Option<Move> {
+    get_legal_moves(game).into_iter().find(|&chess_move| {
+        let mut game = game.clone();
+        game.make_move(chess_move);
+        game.previous_move == notation
+    })
+}
+
+/// Plays the moves of a stored history one after another, e.g. to rebuild a game after a
+/// takeback.
+pub fn replay_moves(game: &mut Game, moves: &[Move]) -> Result<(), &'static str> {
+    for &chess_move in moves {
+        game.validate_and_make_move(chess_move)
+            .map_err(|_| INVALID_HISTORY_ERROR)?;
+    }
+
+    Ok(())
+}
+
+fn get_pawn_candidates(from: Square, color: Color, candidates: &mut Vec<Square>) {
+    let direction = match color {
+        Color::WHITE => -1,
+        Color::BLACK => 1,
+    };
+
+    get_step_candidates(
+        from,
+        &[
+            (direction, 0),
+            (2 * direction, 0),
+            (direction, 1),
+            (direction, -1),
+        ],
+        candidates,
+    );
+}
+
+// castling is generated once per side, and in Chess960 the king might take a rook right next to
+// it, which is a square the king steps to anyway
+fn get_castling_candidates(from: Square, game: &Game, candidates: &mut Vec<Square>) {
+    for short in [true, false] {
+        let target = game.get_castling_target(from, short);
+        if target != from && !candidates.contains(&target) {
+            candidates.push(target);
+        }
+    }
+}
+
+fn get_step_candidates(from: Square, offsets: &[(i32, i32)], candidates: &mut Vec<Square>) {
+    for (row_offset, col_offset) in offsets {
+        let row = from.row as i32 + row_offset;
+        let col = from.col as i32 + col_offset;
+        if is_in_bounds(row, col) {
+            candidates.push(Square::new(row as usize, col as usize));
+        }
+    }
+}
+
+fn get_slide_candidates(
+    from: Square,
+    directions: &[(i32, i32)],
+    game: &Game,
+    candidates: &mut Vec<Square>,
+) {
+    for (row_direction, col_direction) in directions {
+        let mut row = from.row as i32 + row_direction;
+        let mut col = from.col as i32 + col_direction;
+        while is_in_bounds(row, col) {
+            candidates.push(Square::new(row as usize, col as usize));
+            if game.field[row as usize][col as usize].is_some() {
+                break;
+            }
+            row += row_direction;
+            col += col_direction;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_move_gen {
+    use crate::game::{
+        chess_move::Move,
+        fen::{game_from_fen, get_fen},
+        move_gen::{find_move_by_notation, get_legal_moves, replay_moves},
+        Game,
+    };
+
+    #[test]
+    fn test_legal_moves_of_starting_position() {
+        let mut game = Game::new();
+        let legal_moves = get_legal_moves(&mut game);
+
+        assert_eq!(legal_moves.len(), 20);
+        assert!(legal_moves.contains(&Move::from_uci("g1f3").unwrap()));
+        assert!(!legal_moves.contains(&Move::from_uci("e2e5").unwrap()));
+    }
+
+    #[test]
+    fn test_find_move_by_notation() {
+        let mut game = game_from_fen("r3k3/1P6/8/8/8/8/8/4K2R w Kq - 0 1").unwrap();
+
+        assert_eq!(
+            find_move_by_notation(&mut game, "0-0"),
+            Some(Move::from_uci("e1g1").unwrap())
+        );
+        assert_eq!(
+            find_move_by_notation(&mut game, "bxa8=Q+"),
+            Some(Move::from_uci("b7a8q").unwrap())
+        );
+        assert_eq!(
+            find_move_by_notation(&mut game, "b8=N"),
+            Some(Move::from_uci("b7b8n").unwrap())
+        );
+        assert_eq!(find_move_by_notation(&mut game, "Rh8"), None);
+    }
+
+    #[test]
+    fn test_find_ambiguous_moves() {
+        // two knights can go to d2 and two pawns can take on d5, the rooks share a file
+        let mut game = game_from_fen("4k3/8/8/3p4/2P1P3/R7/8/RN2KN2 w - - 0 1").unwrap();
+
+        assert_eq!(
+            find_move_by_notation(&mut game, "Nbd2"),
+            Some(Move::from_uci("b1d2").unwrap())
+        );
+        assert_eq!(
+            find_move_by_notation(&mut game, "Nfe3"),
+            None,
+            "only one knight can go to e3"
+        );
+        assert_eq!(
+            find_move_by_notation(&mut game, "Ne3"),
+            Some(Move::from_uci("f1e3").unwrap())
+        );
+        assert_eq!(
+            find_move_by_notation(&mut game, "cxd5"),
+            Some(Move::from_uci("c4d5").unwrap())
+        );
+        assert_eq!(
+            find_move_by_notation(&mut game, "exd5"),
+            Some(Move::from_uci("e4d5").unwrap())
+        );
+        assert_eq!(
+            find_move_by_notation(&mut game, "R3a2"),
+            Some(Move::from_uci("a3a2").unwrap())
+        );
+        assert_eq!(find_move_by_notation(&mut game, "Nd2"), None);
+        assert_eq!(find_move_by_notation(&mut game, "xd5"), None);
+    }
+
+    #[test]
+    fn test_replay_moves() {
+        let moves: Vec<Move> = [
+            "e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6", "b5a4", "g8f6", "e1g1",
+        ]
+        .iter()
+        .map(|uci| Move::from_uci(uci).unwrap())
+        .collect();
+        let mut game = Game::new();
+        replay_moves(&mut game, &moves).unwrap();
+        assert_eq!(
+            get_fen(&game),
+            "r1bqkb1r/1ppp1ppp/p1n2n2/4p3/B3P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 0 5"
+        );
+
+        let mut game = Game::new();
+        assert!(replay_moves(&mut game, &[moves[0], moves[0]]).is_err());
+    }
+}
@@ -0,0 +1,359 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use serde::Serialize;
+
+use crate::game::{chess_move::Move, Game};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Opening {
+    pub eco: &'static str,
+    pub name: &'static str,
+}
+
+// ECO code, name and the moves in UCI notation. Openings are recognized by their position, so
+// they are also found when the game transposes into them
+const OPENINGS: [(&str, &str, &str); 92] = [
+    ("A00", "Polish Opening", "b2b4"),
+    ("A00", "Grob Opening", "g2g4"),
+    ("A00", "Hungarian Opening", "g2g3"),
+    ("A00", "Van't Kruijs Opening", "e2e3"),
+    ("A00", "Mieses Opening", "d2d3"),
+    ("A01", "Nimzo-Larsen Attack", "b2b3"),
+    ("A02", "Bird Opening", "f2f4"),
+    ("A04", "Zukertort Opening", "g1f3"),
+    ("A09", "Réti Opening", "g1f3 d7d5 c2c4"),
+    ("A10", "English Opening", "c2c4"),
+    (
+        "A20",
+        "English Opening: King's English Variation",
+        "c2c4 e7e5",
+    ),
+    ("A30", "English Opening: Symmetrical Variation", "c2c4 c7c5"),
+    ("A40", "Queen's Pawn Game", "d2d4"),
+    ("A43", "Old Benoni Defense", "d2d4 c7c5"),
+    ("A45", "Indian Defense", "d2d4 g8f6"),
+    ("A45", "Indian Defense: Trompowsky Attack", "d2d4 g8f6 c1g5"),
+    (
+        "A48",
+        "Indian Defense: London System",
+        "d2d4 g8f6 g1f3 g7g6 c1f4",
+    ),
+    ("A51", "Budapest Defense", "d2d4 g8f6 c2c4 e7e5"),
+    ("A56", "Benoni Defense", "d2d4 g8f6 c2c4 c7c5"),
+    ("A57", "Benko Gambit", "d2d4 g8f6 c2c4 c7c5 d4d5 b7b5"),
+    ("A60", "Modern Benoni", "d2d4 g8f6 c2c4 c7c5 d4d5 e7e6"),
+    ("A80", "Dutch Defense", "d2d4 f7f5"),
+    ("B00", "King's Pawn Game", "e2e4"),
+    ("B00", "Nimzowitsch Defense", "e2e4 b8c6"),
+    ("B01", "Scandinavian Defense", "e2e4 d7d5"),
+    (
+        "B01",
+        "Scandinavian Defense: Mieses-Kotroc Variation",
+        "e2e4 d7d5 e4d5 d8d5",
+    ),
+    ("B02", "Alekhine Defense", "e2e4 g8f6"),
+    ("B06", "Modern Defense", "e2e4 g7g6"),
+    ("B07", "Pirc Defense", "e2e4 d7d6 d2d4 g8f6"),
+    ("B10", "Caro-Kann Defense", "e2e4 c7c6"),
+    (
+        "B12",
+        "Caro-Kann Defense: Advance Variation",
+        "e2e4 c7c6 d2d4 d7d5 e4e5",
+    ),
+    (
+        "B13",
+        "Caro-Kann Defense: Exchange Variation",
+        "e2e4 c7c6 d2d4 d7d5 e4d5 c6d5",
+    ),
+    ("B20", "Sicilian Defense", "e2e4 c7c5"),
+    (
+        "B22",
+        "Sicilian Defense: Alapin Variation",
+        "e2e4 c7c5 c2c3",
+    ),
+    ("B23", "Sicilian Defense: Closed", "e2e4 c7c5 b1c3"),
+    ("B27", "Sicilian Defense", "e2e4 c7c5 g1f3"),
+    (
+        "B30",
+        "Sicilian Defense: Old Sicilian",
+        "e2e4 c7c5 g1f3 b8c6",
+    ),
+    (
+        "B32",
+        "Sicilian Defense: Open",
+        "e2e4 c7c5 g1f3 b8c6 d2d4 c5d4 f3d4",
+    ),
+    (
+        "B33",
+        "Sicilian Defense: Sveshnikov Variation",
+        "e2e4 c7c5 g1f3 b8c6 d2d4 c5d4 f3d4 g8f6 b1c3 e7e5",
+    ),
+    (
+        "B40",
+        "Sicilian Defense: French Variation",
+        "e2e4 c7c5 g1f3 e7e6",
+    ),
+    (
+        "B50",
+        "Sicilian Defense: Modern Variations",
+        "e2e4 c7c5 g1f3 d7d6",
+    ),
+    (
+        "B54",
+        "Sicilian Defense: Open",
+        "e2e4 c7c5 g1f3 d7d6 d2d4 c5d4 f3d4",
+    ),
+    (
+        "B70",
+        "Sicilian Defense: Dragon Variation",
+        "e2e4 c7c5 g1f3 d7d6 d2d4 c5d4 f3d4 g8f6 b1c3 g7g6",
+    ),
+    (
+        "B80",
+        "Sicilian Defense: Scheveningen Variation",
+        "e2e4 c7c5 g1f3 d7d6 d2d4 c5d4 f3d4 g8f6 b1c3 e7e6",
+    ),
+    (
+        "B90",
+        "Sicilian Defense: Najdorf Variation",
+        "e2e4 c7c5 g1f3 d7d6 d2d4 c5d4 f3d4 g8f6 b1c3 a7a6",
+    ),
+    ("C00", "French Defense", "e2e4 e7e6"),
+    (
+        "C01",
+        "French Defense: Exchange Variation",
+        "e2e4 e7e6 d2d4 d7d5 e4d5 e6d5",
+    ),
+    (
+        "C02",
+        "French Defense: Advance Variation",
+        "e2e4 e7e6 d2d4 d7d5 e4e5",
+    ),
+    (
+        "C03",
+        "French Defense: Tarrasch Variation",
+        "e2e4 e7e6 d2d4 d7d5 b1d2",
+    ),
+    (
+        "C10",
+        "French Defense: Paulsen Variation",
+        "e2e4 e7e6 d2d4 d7d5 b1c3",
+    ),
+    (
+        "C11",
+        "French Defense: Classical Variation",
+        "e2e4 e7e6 d2d4 d7d5 b1c3 g8f6",
+    ),
+    (
+        "C15",
+        "French Defense: Winawer Variation",
+        "e2e4 e7e6 d2d4 d7d5 b1c3 f8b4",
+    ),
+    ("C20", "King's Pawn Game", "e2e4 e7e5"),
+    ("C21", "Center Game", "e2e4 e7e5 d2d4 e5d4"),
+    ("C23", "Bishop's Opening", "e2e4 e7e5 f1c4"),
+    ("C25", "Vienna Game", "e2e4 e7e5 b1c3"),
+    ("C30", "King's Gambit", "e2e4 e7e5 f2f4"),
+    ("C33", "King's Gambit Accepted", "e2e4 e7e5 f2f4 e5f4"),
+    ("C40", "King's Knight Opening", "e2e4 e7e5 g1f3"),
+    ("C41", "Philidor Defense", "e2e4 e7e5 g1f3 d7d6"),
+    ("C42", "Petrov's Defense", "e2e4 e7e5 g1f3 g8f6"),
+    (
+        "C44",
+        "King's Knight Opening: Normal Variation",
+        "e2e4 e7e5 g1f3 b8c6",
+    ),
+    ("C44", "Scotch Game", "e2e4 e7e5 g1f3 b8c6 d2d4"),
+    ("C45", "Scotch Game", "e2e4 e7e5 g1f3 b8c6 d2d4 e5d4 f3d4"),
+    ("C47", "Four Knights Game", "e2e4 e7e5 g1f3 b8c6 b1c3 g8f6"),
+    ("C50", "Italian Game", "e2e4 e7e5 g1f3 b8c6 f1c4"),
+    (
+        "C50",
+        "Italian Game: Giuoco Piano",
+        "e2e4 e7e5 g1f3 b8c6 f1c4 f8c5",
+    ),
+    (
+        "C51",
+        "Italian Game: Evans Gambit",
+        "e2e4 e7e5 g1f3 b8c6 f1c4 f8c5 b2b4",
+    ),
+    (
+        "C55",
+        "Italian Game: Two Knights Defense",
+        "e2e4 e7e5 g1f3 b8c6 f1c4 g8f6",
+    ),
+    (
+        "C57",
+        "Italian Game: Two Knights Defense, Knight Attack",
+        "e2e4 e7e5 g1f3 b8c6 f1c4 g8f6 f3g5",
+    ),
+    ("C60", "Ruy Lopez", "e2e4 e7e5 g1f3 b8c6 f1b5"),
+    (
+        "C65",
+        "Ruy Lopez: Berlin Defense",
+        "e2e4 e7e5 g1f3 b8c6 f1b5 g8f6",
+    ),
+    (
+        "C68",
+        "Ruy Lopez: Exchange Variation",
+        "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5c6",
+    ),
+    (
+        "C70",
+        "Ruy Lopez: Morphy Defense",
+        "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6",
+    ),
+    (
+        "C84",
+        "Ruy Lopez: Closed",
+        "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1 f8e7",
+    ),
+    ("D00", "Queen's Pawn Game", "d2d4 d7d5"),
+    (
+        "D00",
+        "Queen's Pawn Game: Accelerated London System",
+        "d2d4 d7d5 c1f4",
+    ),
+    (
+        "D02",
+        "Queen's Pawn Game: London System",
+        "d2d4 d7d5 g1f3 g8f6 c1f4",
+    ),
+    ("D06", "Queen's Gambit", "d2d4 d7d5 c2c4"),
+    (
+        "D07",
+        "Queen's Gambit Declined: Chigorin Defense",
+        "d2d4 d7d5 c2c4 b8c6",
+    ),
+    (
+        "D08",
+        "Queen's Gambit Declined: Albin Countergambit",
+        "d2d4 d7d5 c2c4 e7e5",
+    ),
+    ("D10", "Slav Defense", "d2d4 d7d5 c2c4 c7c6"),
+    ("D20", "Queen's Gambit Accepted", "d2d4 d7d5 c2c4 d5c4"),
+    ("D30", "Queen's Gambit Declined", "d2d4 d7d5 c2c4 e7e6"),
+    (
+        "D43",
+        "Semi-Slav Defense",
+        "d2d4 d7d5 c2c4 e7e6 b1c3 g8f6 g1f3 c7c6",
+    ),
+    ("D80", "Grünfeld Defense", "d2d4 g8f6 c2c4 g7g6 b1c3 d7d5"),
+    ("E00", "Catalan Opening", "d2d4 g8f6 c2c4 e7e6 g2g3"),
+    (
+        "E10",
+        "Indian Defense: Anti-Nimzo-Indian",
+        "d2d4 g8f6 c2c4 e7e6 g1f3",
+    ),
+    (
+        "E12",
+        "Queen's Indian Defense",
+        "d2d4 g8f6 c2c4 e7e6 g1f3 b7b6",
+    ),
+    (
+        "E20",
+        "Nimzo-Indian Defense",
+        "d2d4 g8f6 c2c4 e7e6 b1c3 f8b4",
+    ),
+    ("E60", "King's Indian Defense", "d2d4 g8f6 c2c4 g7g6"),
+    (
+        "E70",
+        "King's Indian Defense: Normal Variation",
+        "d2d4 g8f6 c2c4 g7g6 b1c3 f8g7 e2e4 d7d6",
+    ),
+];
+
+/// Names the opening of a game from its moves. The deepest position of the game that is in the
+/// opening table wins, so the name gets more specific as long as the game follows known lines.
+pub fn classify_opening(moves: &[Move]) -> Option<Opening> {
+    let openings = get_openings();
+    let mut game = Game::new();
+    let mut opening = None;
+
+    for &chess_move in moves {
+        // a move that can't be replayed ends the classification at the position before it
+        if game.validate_and_make_move(chess_move).is_err() {
+            break;
+        }
+        if let Some(&known_opening) = openings.get(&game.hash) {
+            opening = Some(known_opening);
+        }
+    }
+
+    opening
+}
+
+// the table is played through once and kept as a map from the position's hash to its opening
+fn get_openings() -> &'static HashMap<u64, Opening> {
+    static OPENING_POSITIONS: OnceLock<HashMap<u64, Opening>> = OnceLock::new();
+
+    OPENING_POSITIONS.get_or_init(|| {
+        let mut openings = HashMap::new();
+        for (eco, name, moves) in OPENINGS {
+            let mut game = Game::new();
+            for uci in moves.split_whitespace() {
+                let chess_move = Move::from_uci(uci).expect("Invalid move in opening table");
+                game.validate_and_make_move(chess_move)
+                    .expect("Illegal move in opening table");
+            }
+            openings.insert(game.hash, Opening { eco, name });
+        }
+
+        openings
+    })
+}
+
+#[cfg(test)]
+mod test_opening {
+    use crate::game::{
+        chess_move::Move,
+        opening::{classify_opening, get_openings, OPENINGS},
+    };
+
+    fn classify(moves: &str) -> Option<(&'static str, &'static str)> {
+        let moves: Vec<Move> = moves
+            .split_whitespace()
+            .map(|uci| Move::from_uci(uci).unwrap())
+            .collect();
+        classify_opening(&moves).map(|opening| (opening.eco, opening.name))
+    }
+
+    #[test]
+    fn test_opening_table() {
+        // every line needs to reach its own position, otherwise one opening hides another
+        assert_eq!(get_openings().len(), OPENINGS.len());
+    }
+
+    #[test]
+    fn test_classify_opening() {
+        assert_eq!(classify(""), None);
+        assert_eq!(classify("e2e4"), Some(("B00", "King's Pawn Game")));
+        assert_eq!(
+            classify("e2e4 c7c5 g1f3 d7d6 d2d4 c5d4 f3d4 g8f6 b1c3 a7a6"),
+            Some(("B90", "Sicilian Defense: Najdorf Variation"))
+        );
+        // later moves outside the table keep the last known opening
+        assert_eq!(
+            classify("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1 f8e7 f1e1 b7b5"),
+            Some(("C84", "Ruy Lopez: Closed"))
+        );
+    }
+
+    #[test]
+    fn test_classify_transposition() {
+        assert_eq!(
+            classify("g1f3 g8f6 c2c4 e7e6 d2d4"),
+            Some(("E10", "Indian Defense: Anti-Nimzo-Indian"))
+        );
+    }
+
+    #[test]
+    fn test_classify_unknown_moves() {
+        assert_eq!(
+            classify("e2e4 e7e5 e1e2"),
+            Some(("C20", "King's Pawn Game"))
+        );
+        assert_eq!(classify("e2e4 g1f3"), Some(("B00", "King's Pawn Game")));
+        assert_eq!(classify("a2a3"), None);
+    }
+}
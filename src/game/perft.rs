@@ -0,0 +1,96 @@
+use crate::game::{move_gen::get_legal_moves, Game};
+
+/// Counts the leaf nodes of the move tree up to the given depth. Comparing the numbers against
+/// the well known results for a position finds bugs in move validation that are hard to spot
+/// in single games.
+pub fn perft(game: &mut Game, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let legal_moves = get_legal_moves(game);
+    if depth == 1 {
+        return legal_moves.len() as u64;
+    }
+
+    let mut nodes = 0;
+    for chess_move in legal_moves {
+        let undo = game.apply_move(chess_move);
+        nodes += perft(game, depth - 1);
+        game.unmake_move(&undo);
+    }
+
+    nodes
+}
+
+/// Runs perft for every legal move on its own, which narrows a wrong node count down to the
+/// move that causes it. Moves are named in UCI notation, e.g. `e7e8q`.
+pub fn perft_divide(game: &mut Game, depth: u32) -> Vec<(String, u64)> {
+    let mut divided = vec![];
+
+    for chess_move in get_legal_moves(game) {
+        let undo = game.apply_move(chess_move);
+        divided.push((chess_move.to_uci(), perft(game, depth.saturating_sub(1))));
+        game.unmake_move(&undo);
+    }
+
+    divided
+}
+
+#[cfg(test)]
+mod test_perft {
+    use crate::game::{
+        fen::{game_from_fen, STARTING_FEN},
+        perft::perft,
+    };
+
+    const KIWIPETE_FEN: &str =
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    const POSITION_3_FEN: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+    const POSITION_4_FEN: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+    const POSITION_5_FEN: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+    const POSITION_6_FEN: &str =
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+    fn assert_perft(fen: &str, expected_nodes: &[u64]) {
+        let mut game = game_from_fen(fen).expect("Expected FEN to be valid");
+        for (depth, expected) in expected_nodes.iter().enumerate() {
+            assert_eq!(
+                perft(&mut game, depth as u32 + 1),
+                *expected,
+                "Wrong node count at depth {}",
+                depth + 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_perft_starting_position() {
+        assert_perft(STARTING_FEN, &[20, 400, 8902, 197281]);
+    }
+
+    #[test]
+    fn test_perft_kiwipete() {
+        assert_perft(KIWIPETE_FEN, &[48, 2039, 97862]);
+    }
+
+    #[test]
+    fn test_perft_position_3() {
+        assert_perft(POSITION_3_FEN, &[14, 191, 2812, 43238]);
+    }
+
+    #[test]
+    fn test_perft_position_4() {
+        assert_perft(POSITION_4_FEN, &[6, 264, 9467]);
+    }
+
+    #[test]
+    fn test_perft_position_5() {
+        assert_perft(POSITION_5_FEN, &[44, 1486, 62379]);
+    }
+
+    #[test]
+    fn test_perft_position_6() {
+        assert_perft(POSITION_6_FEN, &[46, 2079, 89890]);
+    }
+}
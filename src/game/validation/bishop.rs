@@ -1,15 +1,11 @@
 use crate::{
-    game::Game,
+    game::{chess_move::Square, Game},
     utils::error::{CAPTURE_OWN_PIECE_ERROR, GENERAL_ERROR, PIECE_IN_THE_WAY_ERROR},
 };
 
-pub fn validate_bishop_move(
-    from: (usize, usize),
-    to: (usize, usize),
-    game: &Game,
-) -> Result<(), &'static str> {
-    let row_diff = from.0 as i32 - to.0 as i32;
-    let col_diff = from.1 as i32 - to.1 as i32;
+pub fn validate_bishop_move(from: Square, to: Square, game: &Game) -> Result<(), &'static str> {
+    let row_diff = from.row as i32 - to.row as i32;
+    let col_diff = from.col as i32 - to.col as i32;
 
     // not even diagonal move
     if (row_diff).abs() != (col_diff).abs() || row_diff == 0 {
@@ -20,8 +16,8 @@ pub fn validate_bishop_move(
     let row_direction_sign = row_diff / -row_diff.abs();
     let col_direction_sign = col_diff / -col_diff.abs();
     for i in 1..row_diff.abs() {
-        if game.field[(from.0 as i32 + i * row_direction_sign) as usize]
-            [(from.1 as i32 + i * col_direction_sign) as usize]
+        if game.field[(from.row as i32 + i * row_direction_sign) as usize]
+            [(from.col as i32 + i * col_direction_sign) as usize]
             .is_some()
         {
             return Err(PIECE_IN_THE_WAY_ERROR);
@@ -29,7 +25,7 @@ pub fn validate_bishop_move(
     }
 
     // if you capture a piece, is it of the opposite color?
-    if let Some(piece) = game.field[to.0][to.1] {
+    if let Some(piece) = game.field[to.row][to.col] {
         if piece.color == game.next_to_move {
             return Err(CAPTURE_OWN_PIECE_ERROR);
         }
@@ -41,6 +37,7 @@ pub fn validate_bishop_move(
 #[cfg(test)]
 mod test_bishop {
     use crate::{
+        game::chess_move::Move,
         game::chess_piece::{Color, Piece},
         game::ChessPiece,
         game::Game,
@@ -50,7 +47,7 @@ mod test_bishop {
     fn test_bishop_move() {
         let mut game = Game::new();
         game.field[6][4] = None;
-        let val = game.validate_and_make_move("f1", "b5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("f1b5").unwrap());
         if let Err(e) = val {
             panic!("Expected bishop move to be performed, got {:?}", e);
         }
@@ -76,7 +73,7 @@ mod test_bishop {
             piece: Piece::PAWN,
             color: Color::BLACK,
         });
-        let val = game.validate_and_make_move("f1", "b5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("f1b5").unwrap());
         if let Err(e) = val {
             panic!("Expected bishop move to be performed, got {:?}", e);
         }
@@ -102,7 +99,7 @@ mod test_bishop {
             piece: Piece::PAWN,
             color: Color::WHITE,
         });
-        let val = game.validate_and_make_move("f1", "b5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("f1b5").unwrap());
         if val.is_ok() {
             panic!("Expected bishop move to fail due to your own piece being captured");
         }
@@ -111,7 +108,7 @@ mod test_bishop {
     #[test]
     fn test_bishop_move_with_piece_in_the_way() {
         let mut game = Game::new();
-        let val = game.validate_and_make_move("f1", "b5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("f1b5").unwrap());
         if val.is_ok() {
             panic!("Expected bishop move to fail due to a piece being in the way");
         }
@@ -1,9 +1,10 @@
 use crate::{
     game::{
+        chess_move::{Move, Square},
         chess_piece::{ChessPiece, Color, Piece},
         Game,
     },
-    utils::{convert_notation::get_notation_from_square, is_in_bounds},
+    utils::is_in_bounds,
 };
 
 pub struct CapturePiece {
@@ -12,104 +13,55 @@ pub struct CapturePiece {
     pub piece: Piece,
 }
 
-pub fn is_mate(game: &Game) -> bool {
-    let enemy_color: Color;
-    let king_position: (usize, usize);
-
-    match game.next_to_move {
-        Color::WHITE => {
-            enemy_color = Color::BLACK;
-            king_position = game.king_position.white_king_position;
-        }
+pub(crate) const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (2, 1),
+    (2, -1),
+    (1, 2),
+    (1, -2),
+    (-2, 1),
+    (-2, -1),
+    (-1, 2),
+    (-1, -2),
+];
+pub(crate) const KING_OFFSETS: [(i32, i32); 8] = [
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+pub(crate) const DIAGONAL_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+pub(crate) const LINEAR_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+pub fn can_king_be_captured_after_move(game: &mut Game, chess_move: Move) -> Vec<CapturePiece> {
+    let undo = game.apply_move(chess_move);
+    let capturable_by = match game.next_to_move {
         Color::BLACK => {
-            enemy_color = Color::WHITE;
-            king_position = game.king_position.black_king_position;
+            can_be_captured_by(Color::BLACK, game.king_position.white_king_position, game)
         }
-    }
-
-    let threatening_pieces = can_be_captured_by(enemy_color, king_position, game);
-    if threatening_pieces.len() == 0 {
-        return false;
-    }
-
-    let king_row = king_position.0 as i32;
-    let king_col = king_position.1 as i32;
-
-    //Check all surrounding pieces
-    let surrounding_squares: Vec<(i32, i32)> = vec![
-        (king_row + 1, king_col - 1),
-        (king_row + 1, king_col),
-        (king_row + 1, king_col + 1),
-        (king_row, king_col - 1),
-        (king_row, king_col + 1),
-        (king_row - 1, king_col - 1),
-        (king_row - 1, king_col),
-        (king_row - 1, king_col + 1),
-    ];
-    for surr_sq in surrounding_squares {
-        if is_in_bounds(surr_sq.0, surr_sq.1)
-            && can_be_captured_by(enemy_color, (surr_sq.0 as usize, surr_sq.1 as usize), game).len()
-                == 0
-        {
-            return false;
+        Color::WHITE => {
+            can_be_captured_by(Color::WHITE, game.king_position.black_king_position, game)
         }
-    }
-
-    // if there are more than 2 pieces threatening the king and he cannot move to another
-    // square, its mate
-    if threatening_pieces.len() > 1 {
-        return true;
-    }
+    };
+    game.unmake_move(&undo);
 
-    // else we need to check if this one threatening piece can be captured to avoid mate
-    let saving_pieces = can_be_captured_by(
-        game.next_to_move,
-        (threatening_pieces[0].row, threatening_pieces[0].col),
-        game,
-    );
-
-    // and then check if after the "saving move" the king is still in check
-    for piece in saving_pieces {
-        let algebraic_from = get_notation_from_square((piece.row, piece.col)).unwrap();
-        let algebraic_to =
-            get_notation_from_square((threatening_pieces[0].row, threatening_pieces[0].col))
-                .unwrap();
-        println!("{:?} {:?}", algebraic_from, algebraic_to);
-        if can_king_be_captured_after_move(game, &algebraic_from, &algebraic_to, 'Q').len() == 0 {
-            return false;
-        };
-    }
-
-    true
+    capturable_by
 }
 
-pub fn can_king_be_captured_after_move(
-    game: &Game,
-    algebraic_from: &str,
-    algebraic_to: &str,
-    promotion_ch: char,
-) -> Vec<CapturePiece> {
-    let mut game_clone = game.clone();
-    game_clone.make_move(algebraic_from, algebraic_to, promotion_ch);
-    match game_clone.next_to_move {
-        Color::BLACK => can_be_captured_by(
-            Color::BLACK,
-            game_clone.king_position.white_king_position,
-            &game_clone,
-        ),
-        Color::WHITE => can_be_captured_by(
-            Color::WHITE,
-            game_clone.king_position.black_king_position,
-            &game_clone,
-        ),
-    }
+/// Whether the king of the given side is attacked by the other side.
+pub fn is_king_attacked(game: &Game, color: Color) -> bool {
+    let (enemy_color, king_position) = match color {
+        Color::WHITE => (Color::BLACK, game.king_position.white_king_position),
+        Color::BLACK => (Color::WHITE, game.king_position.black_king_position),
+    };
+
+    !can_be_captured_by(enemy_color, king_position, game).is_empty()
 }
 
-pub fn can_be_captured_by(
-    enemy_color: Color,
-    square: (usize, usize),
-    game: &Game,
-) -> Vec<CapturePiece> {
+pub fn can_be_captured_by(enemy_color: Color, square: Square, game: &Game) -> Vec<CapturePiece> {
     let mut capturable_by = vec![];
 
     capturable_by_knight(enemy_color, square, game, &mut capturable_by);
@@ -121,25 +73,15 @@ pub fn can_be_captured_by(
 
 fn capturable_by_knight(
     enemy_color: Color,
-    square: (usize, usize),
+    square: Square,
     game: &Game,
     capturable_by: &mut Vec<CapturePiece>,
 ) {
-    let row = square.0 as i32;
-    let col = square.1 as i32;
+    let row = square.row as i32;
+    let col = square.col as i32;
 
-    let knight_squares = vec![
-        (row + 2, col + 1),
-        (row + 2, col - 1),
-        (row + 1, col + 2),
-        (row + 1, col - 2),
-        (row - 2, col + 1),
-        (row - 2, col - 1),
-        (row - 1, col + 2),
-        (row - 1, col - 2),
-    ];
-
-    for (row, col) in knight_squares {
+    for (row_offset, col_offset) in KNIGHT_OFFSETS {
+        let (row, col) = (row + row_offset, col + col_offset);
         if !is_in_bounds(row, col) {
             continue;
         }
@@ -161,15 +103,14 @@ fn capturable_by_knight(
 
 fn capturable_by_diagonal_move(
     enemy_color: Color,
-    square: (usize, usize),
+    square: Square,
     game: &Game,
     capturable_by: &mut Vec<CapturePiece>,
 ) {
-    let row = square.0 as i32;
-    let col = square.1 as i32;
+    let row = square.row as i32;
+    let col = square.col as i32;
 
-    let directions = vec![(1, 1), (1, -1), (-1, 1), (-1, -1)];
-    'outer: for dir in directions {
+    'outer: for dir in DIAGONAL_DIRECTIONS {
         for i in 1..8 {
             if !is_in_bounds(row + i * dir.0, col + i * dir.1) {
                 continue 'outer;
@@ -198,31 +139,25 @@ fn capturable_by_diagonal_move(
                             piece: Piece::BISHOP,
                         });
                     }
-                    Piece::PAWN => {
-                        if i == 1 {
-                            match (dir.0, dir.1, enemy_color) {
-                                (-1, 1, Color::BLACK)
-                                | (-1, -1, Color::BLACK)
-                                | (1, 1, Color::WHITE)
-                                | (1, -1, Color::WHITE) => {
-                                    capturable_by.push(CapturePiece {
-                                        row,
-                                        col,
-                                        piece: Piece::PAWN,
-                                    });
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    Piece::KING => {
-                        if i == 1 {
+                    Piece::PAWN if i == 1 => match (dir.0, dir.1, enemy_color) {
+                        (-1, 1, Color::BLACK)
+                        | (-1, -1, Color::BLACK)
+                        | (1, 1, Color::WHITE)
+                        | (1, -1, Color::WHITE) => {
                             capturable_by.push(CapturePiece {
                                 row,
                                 col,
-                                piece: Piece::KING,
+                                piece: Piece::PAWN,
                             });
                         }
+                        _ => (),
+                    },
+                    Piece::KING if i == 1 => {
+                        capturable_by.push(CapturePiece {
+                            row,
+                            col,
+                            piece: Piece::KING,
+                        });
                     }
                     _ => (),
                 }
@@ -234,15 +169,14 @@ fn capturable_by_diagonal_move(
 
 fn capturable_by_linear_move(
     enemy_color: Color,
-    square: (usize, usize),
+    square: Square,
     game: &Game,
     capturable_by: &mut Vec<CapturePiece>,
 ) {
-    let row = square.0 as i32;
-    let col = square.1 as i32;
+    let row = square.row as i32;
+    let col = square.col as i32;
 
-    let directions = vec![(1, 0), (-1, 0), (0, 1), (0, -1)];
-    'outer: for dir in directions {
+    'outer: for dir in LINEAR_DIRECTIONS {
         for i in 1..8 {
             if !is_in_bounds(row + i * dir.0, col + i * dir.1) {
                 continue 'outer;
@@ -271,14 +205,12 @@ fn capturable_by_linear_move(
                             piece: Piece::ROOK,
                         });
                     }
-                    Piece::KING => {
-                        if i == 1 {
-                            capturable_by.push(CapturePiece {
-                                row,
-                                col,
-                                piece: Piece::KING,
-                            });
-                        }
+                    Piece::KING if i == 1 => {
+                        capturable_by.push(CapturePiece {
+                            row,
+                            col,
+                            piece: Piece::KING,
+                        });
                     }
                     _ => (),
                 }
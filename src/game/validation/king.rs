@@ -1,75 +1,77 @@
 use crate::{
-    game::{chess_piece::Color, Game},
+    game::{
+        chess_move::{Move, Square},
+        chess_piece::Color,
+        Castling, Game,
+    },
     utils::error::{CAPTURE_OWN_PIECE_ERROR, GENERAL_ERROR, INVALID_CASTLE_ERROR},
 };
 
 use super::check_mate::can_be_captured_by;
 
-pub fn validate_king_move(
-    from: (usize, usize),
-    to: (usize, usize),
-    game: &Game,
-) -> Result<(), &'static str> {
-    let row_diff = (from.0 as i32 - to.0 as i32).abs();
-    let col_diff = (from.1 as i32 - to.1 as i32).abs();
+pub fn validate_king_move(from: Square, to: Square, game: &Game) -> Result<(), &'static str> {
+    if let Some(castling) = game.get_castling(Move::new(from, to, None)) {
+        return validate_castle_move(from, castling, game);
+    }
+
+    let row_diff = (from.row as i32 - to.row as i32).abs();
+    let col_diff = (from.col as i32 - to.col as i32).abs();
 
     match (row_diff, col_diff) {
         (1, 0) | (0, 1) | (1, 1) => {
-            if let Some(piece) = game.field[to.0][to.1] {
+            if let Some(piece) = game.field[to.row][to.col] {
                 if piece.color == game.next_to_move {
                     return Err(CAPTURE_OWN_PIECE_ERROR);
                 }
             }
         }
-        (0, 2) => match game.next_to_move {
-            Color::WHITE => {
-                if game.can_castle.white_can_long_castle
-                    && game.field[7][1] == None
-                    && game.field[7][2] == None
-                    && game.field[7][3] == None
-                    && can_be_captured_by(Color::BLACK, (7, 2), game).len() == 0
-                    && can_be_captured_by(Color::BLACK, (7, 3), game).len() == 0
-                    && can_be_captured_by(Color::BLACK, (7, 4), game).len() == 0
-                {
-                    return Ok(());
-                }
-                if game.can_castle.white_can_short_castle
-                    && game.field[7][5] == None
-                    && game.field[7][6] == None
-                {
-                    return Ok(());
-                }
-                return Err(INVALID_CASTLE_ERROR);
-            }
-            Color::BLACK => {
-                if game.can_castle.black_can_long_castle
-                    && game.field[0][1] == None
-                    && game.field[0][2] == None
-                    && game.field[0][3] == None
-                    && can_be_captured_by(Color::WHITE, (0, 2), game).len() == 0
-                    && can_be_captured_by(Color::WHITE, (0, 3), game).len() == 0
-                    && can_be_captured_by(Color::WHITE, (0, 4), game).len() == 0
-                {
-                    return Ok(());
-                }
-                if game.can_castle.black_can_short_castle
-                    && game.field[0][5] == None
-                    && game.field[0][6] == None
-                {
-                    return Ok(());
-                }
-                return Err(INVALID_CASTLE_ERROR);
-            }
-        },
+        // a king move of two squares can only be castling, but the king or rook has moved
+        (0, 2) => return Err(INVALID_CASTLE_ERROR),
         _ => return Err(GENERAL_ERROR),
     }
 
     Ok(())
 }
 
+// the castling right and the rook have already been checked by `get_castling`
+fn validate_castle_move(from: Square, castling: Castling, game: &Game) -> Result<(), &'static str> {
+    let home_row = from.row;
+    let enemy_color = match game.next_to_move {
+        Color::WHITE => Color::BLACK,
+        Color::BLACK => Color::WHITE,
+    };
+
+    // every square the king and the rook pass or land on needs to be empty apart from the king
+    // and the rook themselves. Their paths always touch, so this is one range of squares
+    let cols = [
+        from.col,
+        castling.king_to.col,
+        castling.rook_from.col,
+        castling.rook_to.col,
+    ];
+    let (min_col, max_col) = (*cols.iter().min().unwrap(), *cols.iter().max().unwrap());
+    if (min_col..=max_col).any(|col| {
+        col != from.col && col != castling.rook_from.col && game.field[home_row][col].is_some()
+    }) {
+        return Err(INVALID_CASTLE_ERROR);
+    }
+
+    // the king may not start on, pass through or land on an attacked square
+    let king_cols = from.col.min(castling.king_to.col)..=from.col.max(castling.king_to.col);
+    if king_cols
+        .into_iter()
+        .any(|col| !can_be_captured_by(enemy_color, Square::new(home_row, col), game).is_empty())
+    {
+        return Err(INVALID_CASTLE_ERROR);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test_king {
     use crate::{
+        game::chess_move::{Move, Square},
         game::chess_piece::{Color, Piece},
         game::{CastlingRights, ChessPiece},
     };
@@ -82,7 +84,7 @@ mod test_king {
         game.field[7][3] = None;
         game.field[7][2] = None;
         game.field[7][1] = None;
-        let val = game.validate_and_make_move("e1", "c1", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("e1c1").unwrap());
         if let Err(e) = val {
             panic!("Expected castle move to be performed, got {:?}", e);
         }
@@ -109,7 +111,7 @@ mod test_king {
     fn test_castle_move_with_piece_in_the_way() {
         let mut game = Game::new();
         game.field[7][5] = None;
-        let val = game.validate_and_make_move("e1", "g1", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("e1g1").unwrap());
         if val.is_ok() {
             panic!("Expected castle move to fail due to a piece being in the way");
         }
@@ -121,7 +123,7 @@ mod test_king {
         game.field[0][5] = None;
         game.field[0][6] = None;
         game.field[0][7] = None;
-        let val = game.validate_and_make_move("e8", "g8", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("e8g8").unwrap());
         if val.is_ok() {
             panic!("Expected castle move to fail due to pieces being on the wrong position");
         }
@@ -138,7 +140,7 @@ mod test_king {
         };
         game.field[0][5] = None;
         game.field[0][6] = None;
-        let val = game.validate_and_make_move("e8", "g8", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("e8g8").unwrap());
         if val.is_ok() {
             panic!("Expected castle move to fail due to pieces having already moved before");
         }
@@ -152,9 +154,99 @@ mod test_king {
         game.field[7][1] = None;
         game.field[6][3] = None;
         game.field[1][3] = None;
-        let val = game.validate_and_make_move("e1", "c1", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("e1c1").unwrap());
+        if val.is_ok() {
+            panic!("Expected castle move to fail due to castling through check");
+        }
+    }
+
+    #[test]
+    fn test_long_castle_move_with_only_short_side_free() {
+        let mut game = Game::new();
+        game.field[7][5] = None;
+        game.field[7][6] = None;
+        let val = game.validate_and_make_move(Move::from_uci("e1c1").unwrap());
+        if val.is_ok() {
+            panic!("Expected long castle move to fail due to pieces being in the way");
+        }
+    }
+
+    #[test]
+    fn test_short_castle_move_through_check() {
+        let mut game = Game::new();
+        game.field[7][5] = None;
+        game.field[7][6] = None;
+        game.field[6][4] = None;
+        game.field[4][2] = Some(ChessPiece {
+            color: Color::BLACK,
+            piece: Piece::BISHOP,
+        });
+        let val = game.validate_and_make_move(Move::from_uci("e1g1").unwrap());
         if val.is_ok() {
             panic!("Expected castle move to fail due to castling through check");
         }
     }
+
+    #[test]
+    fn test_castle_move_out_of_check() {
+        let mut game = Game::new();
+        game.field[7][5] = None;
+        game.field[7][6] = None;
+        game.field[6][3] = None;
+        game.field[4][1] = Some(ChessPiece {
+            color: Color::BLACK,
+            piece: Piece::BISHOP,
+        });
+        let val = game.validate_and_make_move(Move::from_uci("e1g1").unwrap());
+        if val.is_ok() {
+            panic!("Expected castle move to fail due to the king being in check");
+        }
+    }
+
+    #[test]
+    fn test_castle_move_without_rook() {
+        let mut game = Game::new();
+        game.field[7][5] = None;
+        game.field[7][6] = None;
+        game.field[7][7] = Some(ChessPiece {
+            color: Color::WHITE,
+            piece: Piece::KNIGHT,
+        });
+        let val = game.validate_and_make_move(Move::from_uci("e1g1").unwrap());
+        if val.is_ok() {
+            panic!("Expected castle move to fail due to the rook not being on its square");
+        }
+    }
+
+    #[test]
+    fn test_castle_move_outside_of_home_square() {
+        let mut game = Game::new();
+        game.field[7][4] = None;
+        game.field[4][4] = Some(ChessPiece {
+            color: Color::WHITE,
+            piece: Piece::KING,
+        });
+        game.king_position.white_king_position = Square::new(4, 4);
+        let val = game.validate_and_make_move(Move::from_uci("e4g4").unwrap());
+        if val.is_ok() {
+            panic!("Expected castle move to fail due to the king not being on its home square");
+        }
+    }
+
+    #[test]
+    fn test_castle_rights_after_rook_capture() {
+        let mut game = Game::new();
+        game.field[6][6] = None;
+        game.field[5][5] = Some(ChessPiece {
+            color: Color::BLACK,
+            piece: Piece::BISHOP,
+        });
+        game.validate_and_make_move(Move::from_uci("a2a3").unwrap())
+            .expect("Expected pawn move to be performed");
+        game.validate_and_make_move(Move::from_uci("f3h1").unwrap())
+            .expect("Expected bishop move to be performed");
+
+        assert!(!game.can_castle.white_can_short_castle);
+        assert!(game.can_castle.white_can_long_castle);
+    }
 }
@@ -1,15 +1,11 @@
 use crate::{
-    game::Game,
+    game::{chess_move::Square, Game},
     utils::error::{CAPTURE_OWN_PIECE_ERROR, GENERAL_ERROR},
 };
 
-pub fn validate_knight_move(
-    from: (usize, usize),
-    to: (usize, usize),
-    game: &Game,
-) -> Result<(), &'static str> {
-    let row_diff = (from.0 as i32 - to.0 as i32).abs();
-    let col_diff = (from.1 as i32 - to.1 as i32).abs();
+pub fn validate_knight_move(from: Square, to: Square, game: &Game) -> Result<(), &'static str> {
+    let row_diff = (from.row as i32 - to.row as i32).abs();
+    let col_diff = (from.col as i32 - to.col as i32).abs();
 
     // not even move
     match (row_diff, col_diff) {
@@ -18,7 +14,7 @@ pub fn validate_knight_move(
     }
 
     // if you capture a piece, is it of the opposite color?
-    if let Some(piece) = game.field[to.0][to.1] {
+    if let Some(piece) = game.field[to.row][to.col] {
         if piece.color == game.next_to_move {
             return Err(CAPTURE_OWN_PIECE_ERROR);
         }
@@ -30,6 +26,7 @@ pub fn validate_knight_move(
 #[cfg(test)]
 mod test_knight {
     use crate::{
+        game::chess_move::Move,
         game::chess_piece::{Color, Piece},
         game::ChessPiece,
         game::Game,
@@ -38,7 +35,7 @@ mod test_knight {
     #[test]
     fn test_knight_move() {
         let mut game = Game::new();
-        let val = game.validate_and_make_move("b1", "c3", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("b1c3").unwrap());
         if let Err(e) = val {
             panic!("Expected knight move to be performed, got {:?}", e);
         }
@@ -63,7 +60,7 @@ mod test_knight {
             piece: Piece::ROOK,
             color: Color::BLACK,
         });
-        let val = game.validate_and_make_move("b1", "d2", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("b1d2").unwrap());
         if let Err(e) = val {
             panic!("Expected knight move to be performed, got {:?}", e);
         }
@@ -84,9 +81,18 @@ mod test_knight {
     #[test]
     fn test_knight_move_with_wrong_capture() {
         let mut game = Game::new();
-        let val = game.validate_and_make_move("b1", "d2", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("b1d2").unwrap());
         if val.is_ok() {
             panic!("Expected knight move to fail due to your own piece being captured");
         }
     }
+
+    #[test]
+    fn test_knight_move_of_opponent() {
+        let mut game = Game::new();
+        let val = game.validate_and_make_move(Move::from_uci("b8c6").unwrap());
+        if val.is_ok() {
+            panic!("Expected knight move to fail due to it being the opponents turn");
+        }
+    }
 }
@@ -1,44 +1,44 @@
 use crate::{
     game::{
+        chess_move::Square,
         chess_piece::{Color, Piece},
         Game,
     },
-    utils::{
-        convert_notation::{get_notation_from_square, get_promotion_piece},
-        error::{CAPTURE_OWN_PIECE_ERROR, GENERAL_ERROR, PIECE_IN_THE_WAY_ERROR, PROMOTION_ERROR},
+    utils::error::{
+        CAPTURE_OWN_PIECE_ERROR, GENERAL_ERROR, PIECE_IN_THE_WAY_ERROR, PROMOTION_ERROR,
     },
 };
 
 pub fn validate_pawn_move(
-    from: (usize, usize),
-    to: (usize, usize),
-    promotion_ch: char,
+    from: Square,
+    to: Square,
+    promotion: Option<Piece>,
     game: &Game,
 ) -> Result<(), &'static str> {
-    let row_diff = from.0 as i32 - to.0 as i32;
-    let col_diff = from.1 as i32 - to.1 as i32;
+    let row_diff = from.row as i32 - to.row as i32;
+    let col_diff = from.col as i32 - to.col as i32;
 
     match (row_diff, col_diff, game.next_to_move) {
         // standard pawn move
         (1, 0, Color::WHITE) | (-1, 0, Color::BLACK) => {
-            if game.field[to.0][to.1].is_some() {
+            if game.field[to.row][to.col].is_some() {
                 return Err(PIECE_IN_THE_WAY_ERROR);
             }
         }
         // 2 squares pawn move
         (-2, 0, Color::BLACK) => {
-            if from.0 != 1 || to.0 != 3 {
+            if !game.get_rules().can_double_step(Color::BLACK, from) {
                 return Err(GENERAL_ERROR);
             }
-            if game.field[to.0][to.1].is_some() || game.field[from.0 + 1][to.1].is_some() {
+            if game.field[to.row][to.col].is_some() || game.field[from.row + 1][to.col].is_some() {
                 return Err(PIECE_IN_THE_WAY_ERROR);
             }
         }
         (2, 0, Color::WHITE) => {
-            if from.0 != 6 || to.0 != 4 {
+            if !game.get_rules().can_double_step(Color::WHITE, from) {
                 return Err(GENERAL_ERROR);
             }
-            if game.field[to.0][to.1].is_some() || game.field[from.0 - 1][to.1].is_some() {
+            if game.field[to.row][to.col].is_some() || game.field[from.row - 1][to.col].is_some() {
                 return Err(PIECE_IN_THE_WAY_ERROR);
             }
         }
@@ -48,17 +48,14 @@ pub fn validate_pawn_move(
         | (-1, -1, Color::BLACK)
         | (-1, 1, Color::BLACK) => {
             // check for wrong capture
-            if let Some(piece) = game.field[to.0][to.1] {
+            if let Some(piece) = game.field[to.row][to.col] {
                 if piece.color == game.next_to_move {
                     return Err(CAPTURE_OWN_PIECE_ERROR);
                 }
             }
 
             // check for en passant
-            if game.field[to.0][to.1].is_none()
-                && (!game.can_en_passant
-                    || game.previous_move != get_notation_from_square((from.0, to.1))?)
-            {
+            if game.field[to.row][to.col].is_none() && game.en_passant_square != Some(to) {
                 return Err(GENERAL_ERROR);
             }
         }
@@ -66,9 +63,11 @@ pub fn validate_pawn_move(
     }
 
     // check for promotion moves
-    let promotion_piece = get_promotion_piece(promotion_ch);
-    if (to.0 == 0 || to.0 == 7) && promotion_piece.is_none() {
-        return Err(PROMOTION_ERROR);
+    if to.row == 0 || to.row == 7 {
+        match promotion {
+            Some(Piece::QUEEN | Piece::ROOK | Piece::BISHOP | Piece::KNIGHT) => (),
+            _ => return Err(PROMOTION_ERROR),
+        }
     }
 
     Ok(())
@@ -77,16 +76,18 @@ pub fn validate_pawn_move(
 #[cfg(test)]
 mod test_pawn {
     use crate::{
+        game::chess_move::{Move, Square},
         game::chess_piece::{Color, Piece},
+        game::fen::game_from_fen,
         game::ChessPiece,
         game::Game,
+        utils::error::PROMOTION_ERROR,
     };
 
     #[test]
     fn test_pawn_move() {
         let mut game = Game::new();
-        let _ = game
-            .validate_and_make_move("b2", "b4", ' ')
+        game.validate_and_make_move(Move::from_uci("b2b4").unwrap())
             .expect("Expected pawn move to be performed");
 
         assert_eq!(game.field[6][1], None);
@@ -105,20 +106,15 @@ mod test_pawn {
     #[test]
     fn test_pawn_move_with_capture() {
         let mut game = Game::new();
-        let _ = game
-            .validate_and_make_move("d2", "d4", ' ')
+        game.validate_and_make_move(Move::from_uci("d2d4").unwrap())
             .expect("Expected pawn move to be performed");
-        let _ = game
-            .validate_and_make_move("d7", "d5", ' ')
+        game.validate_and_make_move(Move::from_uci("d7d5").unwrap())
             .expect("Expected pawn move to be performed");
-        let _ = game
-            .validate_and_make_move("c2", "c4", ' ')
+        game.validate_and_make_move(Move::from_uci("c2c4").unwrap())
             .expect("Expected pawn move to be performed");
-        let _ = game
-            .validate_and_make_move("e7", "e6", ' ')
+        game.validate_and_make_move(Move::from_uci("e7e6").unwrap())
             .expect("Expected pawn move to be performed");
-        let _ = game
-            .validate_and_make_move("c4", "d5", ' ')
+        game.validate_and_make_move(Move::from_uci("c4d5").unwrap())
             .expect("Expected pawn move to be performed");
 
         assert_eq!(game.field[6][2], None);
@@ -134,32 +130,27 @@ mod test_pawn {
                 }
             })
         );
-        assert_eq!(game.previous_move, "xd5");
+        assert_eq!(game.previous_move, "cxd5");
     }
 
     #[test]
     fn test_pawn_move_with_en_passant() {
         let mut game = Game::new();
-        let _ = game
-            .validate_and_make_move("d2", "d4", ' ')
+        game.validate_and_make_move(Move::from_uci("d2d4").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, true);
-        let _ = game
-            .validate_and_make_move("h7", "h6", ' ')
+        assert_eq!(game.en_passant_square, Some(Square::new(5, 3)));
+        game.validate_and_make_move(Move::from_uci("h7h6").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, false);
-        let _ = game
-            .validate_and_make_move("d4", "d5", ' ')
+        assert_eq!(game.en_passant_square, None);
+        game.validate_and_make_move(Move::from_uci("d4d5").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, false);
-        let _ = game
-            .validate_and_make_move("e7", "e5", ' ')
+        assert_eq!(game.en_passant_square, None);
+        game.validate_and_make_move(Move::from_uci("e7e5").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, true);
-        let _ = game
-            .validate_and_make_move("d5", "e6", ' ')
+        assert_eq!(game.en_passant_square, Some(Square::new(2, 4)));
+        game.validate_and_make_move(Move::from_uci("d5e6").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, false);
+        assert_eq!(game.en_passant_square, None);
 
         assert_eq!(game.field[6][3], None);
         assert_eq!(game.field[1][4], None);
@@ -173,29 +164,25 @@ mod test_pawn {
                 }
             })
         );
-        assert_eq!(game.previous_move, "xe6");
+        assert_eq!(game.previous_move, "dxe6");
     }
 
     #[test]
     fn test_pawn_move_with_incorrect_en_passant() {
         let mut game = Game::new();
-        let _ = game
-            .validate_and_make_move("d2", "d4", ' ')
+        game.validate_and_make_move(Move::from_uci("d2d4").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, true);
-        let _ = game
-            .validate_and_make_move("h7", "h6", ' ')
+        assert_eq!(game.en_passant_square, Some(Square::new(5, 3)));
+        game.validate_and_make_move(Move::from_uci("h7h6").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, false);
-        let _ = game
-            .validate_and_make_move("d4", "d5", ' ')
+        assert_eq!(game.en_passant_square, None);
+        game.validate_and_make_move(Move::from_uci("d4d5").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, false);
-        let _ = game
-            .validate_and_make_move("g7", "g5", ' ')
+        assert_eq!(game.en_passant_square, None);
+        game.validate_and_make_move(Move::from_uci("g7g5").unwrap())
             .expect("Expected pawn move to be performed");
-        assert_eq!(game.can_en_passant, true);
-        let val = game.validate_and_make_move("d5", "e6", ' ');
+        assert_eq!(game.en_passant_square, Some(Square::new(2, 6)));
+        let val = game.validate_and_make_move(Move::from_uci("d5e6").unwrap());
         if val.is_ok() {
             panic!("Expected pawn move to fail due to having en passant rights but no pawn present on capture square");
         }
@@ -208,7 +195,7 @@ mod test_pawn {
             piece: Piece::PAWN,
             color: Color::WHITE,
         });
-        let val = game.validate_and_make_move("b2", "c3", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("b2c3").unwrap());
         if val.is_ok() {
             panic!("Expected pawn move to fail due to your own piece being captured");
         }
@@ -221,7 +208,7 @@ mod test_pawn {
             piece: Piece::QUEEN,
             color: Color::BLACK,
         });
-        let val = game.validate_and_make_move("h2", "h4", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("h2h4").unwrap());
         if val.is_ok() {
             panic!("Expected pawn move to fail due to a piece being in the way");
         }
@@ -230,15 +217,74 @@ mod test_pawn {
     #[test]
     fn test_pawn_invalid_two_square_move() {
         let mut game = Game::new();
-        let _ = game
-            .validate_and_make_move("h2", "h3", ' ')
+        game.validate_and_make_move(Move::from_uci("h2h3").unwrap())
             .expect("Expected pawn move to be performed");
-        let _ = game
-            .validate_and_make_move("d7", "d5", ' ')
+        game.validate_and_make_move(Move::from_uci("d7d5").unwrap())
             .expect("Expected pawn move to be performed");
-        let val = game.validate_and_make_move("h3", "h5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("h3h5").unwrap());
         if val.is_ok() {
             panic!("Expected pawn move to fail due to the pawn already being moved from the starting square");
         }
     }
+
+    #[test]
+    fn test_en_passant_resolving_check() {
+        let mut game = game_from_fen("4k3/3p4/8/4P3/4K3/8/8/8 b - - 0 1").unwrap();
+        game.validate_and_make_move(Move::from_uci("d7d5").unwrap())
+            .expect("Expected pawn move to be performed");
+        assert_eq!(game.previous_move, "d5+");
+        assert_eq!(game.en_passant_square, Some(Square::new(2, 3)));
+
+        game.validate_and_make_move(Move::from_uci("e5d6").unwrap())
+            .expect("Expected en passant to resolve the check");
+        assert_eq!(game.field[3][3], None);
+        assert_eq!(game.previous_move, "exd6");
+    }
+
+    #[test]
+    fn test_en_passant_giving_check() {
+        let mut game = game_from_fen("8/3pk3/8/4P3/8/8/8/4K3 b - - 0 1").unwrap();
+        game.validate_and_make_move(Move::from_uci("d7d5").unwrap())
+            .expect("Expected pawn move to be performed");
+        game.validate_and_make_move(Move::from_uci("e5d6").unwrap())
+            .expect("Expected en passant to be performed");
+
+        assert_eq!(game.field[3][3], None);
+        assert_eq!(game.previous_move, "exd6+");
+    }
+
+    #[test]
+    fn test_en_passant_with_pinned_pawn() {
+        let mut game = game_from_fen("8/2p5/8/KP5r/8/8/8/7k b - - 0 1").unwrap();
+        game.validate_and_make_move(Move::from_uci("c7c5").unwrap())
+            .expect("Expected pawn move to be performed");
+        let val = game.validate_and_make_move(Move::from_uci("b5c6").unwrap());
+        if val.is_ok() {
+            panic!("Expected en passant to fail due to exposing the king on the rank");
+        }
+    }
+
+    #[test]
+    fn test_pawn_promotion() {
+        let mut game = game_from_fen("8/4P3/8/8/8/8/k7/4K3 w - - 0 1").unwrap();
+        let val = game.validate_move(Move::from_uci("e7e8").unwrap());
+        assert_eq!(val, Err(PROMOTION_ERROR));
+        let val = game.validate_move(Move::new(
+            Square::new(1, 4),
+            Square::new(0, 4),
+            Some(Piece::KING),
+        ));
+        assert_eq!(val, Err(PROMOTION_ERROR));
+
+        game.validate_and_make_move(Move::from_uci("e7e8n").unwrap())
+            .expect("Expected promotion to be performed");
+        assert_eq!(
+            game.field[0][4],
+            Some(ChessPiece {
+                piece: Piece::KNIGHT,
+                color: Color::WHITE,
+            })
+        );
+        assert_eq!(game.previous_move, "e8=N");
+    }
 }
@@ -1,15 +1,11 @@
 use crate::{
-    game::Game,
+    game::{chess_move::Square, Game},
     utils::error::{CAPTURE_OWN_PIECE_ERROR, GENERAL_ERROR, PIECE_IN_THE_WAY_ERROR},
 };
 
-pub fn validate_queen_move(
-    from: (usize, usize),
-    to: (usize, usize),
-    game: &Game,
-) -> Result<(), &'static str> {
-    let row_diff = from.0 as i32 - to.0 as i32;
-    let col_diff = from.1 as i32 - to.1 as i32;
+pub fn validate_queen_move(from: Square, to: Square, game: &Game) -> Result<(), &'static str> {
+    let row_diff = from.row as i32 - to.row as i32;
+    let col_diff = from.col as i32 - to.col as i32;
     let row_direction_sign: i32;
     let col_direction_sign: i32;
 
@@ -19,7 +15,9 @@ pub fn validate_queen_move(
         (0, _) => {
             col_direction_sign = col_diff / -col_diff.abs();
             for i in 1..col_diff.abs() {
-                if game.field[from.0][(from.1 as i32 + i * col_direction_sign) as usize].is_some() {
+                if game.field[from.row][(from.col as i32 + i * col_direction_sign) as usize]
+                    .is_some()
+                {
                     return Err(PIECE_IN_THE_WAY_ERROR);
                 };
             }
@@ -28,7 +26,9 @@ pub fn validate_queen_move(
         (_, 0) => {
             row_direction_sign = row_diff / -row_diff.abs();
             for i in 1..row_diff.abs() {
-                if game.field[(from.0 as i32 + i * row_direction_sign) as usize][from.1].is_some() {
+                if game.field[(from.row as i32 + i * row_direction_sign) as usize][from.col]
+                    .is_some()
+                {
                     return Err(PIECE_IN_THE_WAY_ERROR);
                 };
             }
@@ -42,8 +42,8 @@ pub fn validate_queen_move(
             row_direction_sign = row_diff / -row_diff.abs();
             col_direction_sign = col_diff / -col_diff.abs();
             for i in 1..row_diff.abs() {
-                if game.field[(from.0 as i32 + i * row_direction_sign) as usize]
-                    [(from.1 as i32 + i * col_direction_sign) as usize]
+                if game.field[(from.row as i32 + i * row_direction_sign) as usize]
+                    [(from.col as i32 + i * col_direction_sign) as usize]
                     .is_some()
                 {
                     return Err(PIECE_IN_THE_WAY_ERROR);
@@ -53,7 +53,7 @@ pub fn validate_queen_move(
     }
 
     // if you capture a piece, is it of the opposite color?
-    if let Some(piece) = game.field[to.0][to.1] {
+    if let Some(piece) = game.field[to.row][to.col] {
         if piece.color == game.next_to_move {
             return Err(CAPTURE_OWN_PIECE_ERROR);
         }
@@ -65,6 +65,7 @@ pub fn validate_queen_move(
 #[cfg(test)]
 mod test_queen {
     use crate::{
+        game::chess_move::Move,
         game::chess_piece::{Color, Piece},
         game::ChessPiece,
         game::Game,
@@ -74,7 +75,7 @@ mod test_queen {
     fn test_queen_move() {
         let mut game = Game::new();
         game.field[6][4] = None;
-        let val = game.validate_and_make_move("d1", "g4", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("d1g4").unwrap());
         if let Err(e) = val {
             panic!("Expected queen move to be performed, got {:?}", e);
         }
@@ -96,7 +97,7 @@ mod test_queen {
     fn test_queen_move_with_capture() {
         let mut game = Game::new();
         game.field[6][3] = None;
-        let val = game.validate_and_make_move("d1", "d7", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("d1d7").unwrap());
         if let Err(e) = val {
             panic!("Expected queen move to be performed, got {:?}", e);
         }
@@ -117,7 +118,7 @@ mod test_queen {
     #[test]
     fn test_queen_move_with_wrong_capture() {
         let mut game = Game::new();
-        let val = game.validate_and_make_move("d1", "d2", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("d1d2").unwrap());
         if val.is_ok() {
             panic!("Expected queen move to fail due to your own piece being captured");
         }
@@ -127,7 +128,7 @@ mod test_queen {
     fn test_queen_move_with_piece_in_the_way() {
         let mut game = Game::new();
         game.field[6][3] = None;
-        let val = game.validate_and_make_move("d1", "d8", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("d1d8").unwrap());
         if val.is_ok() {
             panic!("Expected queen move to fail due to a piece being in the way");
         }
@@ -1,15 +1,11 @@
 use crate::{
-    game::Game,
+    game::{chess_move::Square, Game},
     utils::error::{CAPTURE_OWN_PIECE_ERROR, GENERAL_ERROR, PIECE_IN_THE_WAY_ERROR},
 };
 
-pub fn validate_rook_move(
-    from: (usize, usize),
-    to: (usize, usize),
-    game: &Game,
-) -> Result<(), &'static str> {
-    let row_diff = from.0 as i32 - to.0 as i32;
-    let col_diff = from.1 as i32 - to.1 as i32;
+pub fn validate_rook_move(from: Square, to: Square, game: &Game) -> Result<(), &'static str> {
+    let row_diff = from.row as i32 - to.row as i32;
+    let col_diff = from.col as i32 - to.col as i32;
 
     // not even move
     match (row_diff, col_diff) {
@@ -17,7 +13,9 @@ pub fn validate_rook_move(
         (0, _) => {
             let col_direction_sign = col_diff / -col_diff.abs();
             for i in 1..col_diff.abs() {
-                if game.field[from.0][(from.1 as i32 + i * col_direction_sign) as usize].is_some() {
+                if game.field[from.row][(from.col as i32 + i * col_direction_sign) as usize]
+                    .is_some()
+                {
                     return Err(PIECE_IN_THE_WAY_ERROR);
                 };
             }
@@ -25,7 +23,9 @@ pub fn validate_rook_move(
         (_, 0) => {
             let row_direction_sign = row_diff / -row_diff.abs();
             for i in 1..row_diff.abs() {
-                if game.field[(from.0 as i32 + i * row_direction_sign) as usize][from.1].is_some() {
+                if game.field[(from.row as i32 + i * row_direction_sign) as usize][from.col]
+                    .is_some()
+                {
                     return Err(PIECE_IN_THE_WAY_ERROR);
                 };
             }
@@ -34,7 +34,7 @@ pub fn validate_rook_move(
     }
 
     // if you capture a piece, is it of the opposite color?
-    if let Some(piece) = game.field[to.0][to.1] {
+    if let Some(piece) = game.field[to.row][to.col] {
         if piece.color == game.next_to_move {
             return Err(CAPTURE_OWN_PIECE_ERROR);
         }
@@ -46,6 +46,7 @@ pub fn validate_rook_move(
 #[cfg(test)]
 mod test_rook {
     use crate::{
+        game::chess_move::Move,
         game::chess_piece::{Color, Piece},
         game::ChessPiece,
         game::Game,
@@ -55,7 +56,7 @@ mod test_rook {
     fn test_rook_move() {
         let mut game = Game::new();
         game.field[6][0] = None;
-        let val = game.validate_and_make_move("a1", "a5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("a1a5").unwrap());
         if let Err(e) = val {
             panic!("Expected rook move to be performed, got {:?}", e);
         }
@@ -71,7 +72,7 @@ mod test_rook {
             })
         );
         assert_eq!(game.previous_move, "Ra5");
-        assert_eq!(game.can_castle.white_can_long_castle, false);
+        assert!(!game.can_castle.white_can_long_castle);
     }
 
     #[test]
@@ -83,7 +84,7 @@ mod test_rook {
             color: Color::WHITE,
         });
         game.next_to_move = Color::BLACK;
-        let val = game.validate_and_make_move("a8", "a3", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("a8a3").unwrap());
         if let Err(e) = val {
             panic!("Expected rook move to be performed, got {:?}", e);
         }
@@ -99,8 +100,8 @@ mod test_rook {
             })
         );
         assert_eq!(game.previous_move, "Rxa3");
-        assert_eq!(game.can_castle.black_can_long_castle, false);
-        assert_eq!(game.can_castle.white_can_long_castle, true);
+        assert!(!game.can_castle.black_can_long_castle);
+        assert!(game.can_castle.white_can_long_castle);
     }
 
     #[test]
@@ -111,7 +112,7 @@ mod test_rook {
             piece: Piece::PAWN,
             color: Color::WHITE,
         });
-        let val = game.validate_and_make_move("a1", "a5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("a1a5").unwrap());
         if val.is_ok() {
             panic!("Expected rook move to fail due to your own piece being captured");
         }
@@ -120,7 +121,7 @@ mod test_rook {
     #[test]
     fn test_rook_move_with_piece_in_the_way() {
         let mut game = Game::new();
-        let val = game.validate_and_make_move("a1", "a5", ' ');
+        let val = game.validate_and_make_move(Move::from_uci("a1a5").unwrap());
         if val.is_ok() {
             panic!("Expected rook move to fail due to a piece being in the way");
         }
@@ -0,0 +1,183 @@
+pub mod atomic;
+pub mod horde;
+pub mod king_of_the_hill;
+pub mod three_check;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        chess960::get_random_position,
+        chess_move::{Move, Square},
+        chess_piece::Color,
+        fen::game_from_variant_fen,
+        move_gen::get_legal_moves,
+        validation::check_mate::{can_king_be_captured_after_move, is_king_attacked},
+        Game, MoveUndo,
+    },
+    utils::error::CHECK_ERROR,
+};
+
+use self::{
+    atomic::Atomic,
+    horde::{Horde, HORDE_FEN},
+    king_of_the_hill::KingOfTheHill,
+    three_check::ThreeCheck,
+};
+
+/// The kind of game that is played, picked when the game is created.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Fischer Random, every game starts from one of the 960 shuffled back ranks
+    Chess960,
+    /// a king that reaches one of the four center squares wins
+    KingOfTheHill,
+    /// giving the third check wins
+    ThreeCheck,
+    /// a capture blows up every piece around it but the pawns, and blowing up the king wins
+    Atomic,
+    /// white plays 36 pawns without a king and wins by mate, black wins by taking them all
+    Horde,
+}
+
+const VARIANTS: [Variant; 6] = [
+    Variant::Standard,
+    Variant::Chess960,
+    Variant::KingOfTheHill,
+    Variant::ThreeCheck,
+    Variant::Atomic,
+    Variant::Horde,
+];
+
+impl Variant {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Variant::Standard => "STANDARD",
+            Variant::Chess960 => "CHESS960",
+            Variant::KingOfTheHill => "KING_OF_THE_HILL",
+            Variant::ThreeCheck => "THREE_CHECK",
+            Variant::Atomic => "ATOMIC",
+            Variant::Horde => "HORDE",
+        }
+    }
+    pub fn from_name(name: &str) -> Option<Variant> {
+        VARIANTS
+            .into_iter()
+            .find(|variant| variant.as_str() == name)
+    }
+    pub fn get_rules(&self) -> &'static dyn Rules {
+        match self {
+            Variant::Standard | Variant::Chess960 => &Standard,
+            Variant::KingOfTheHill => &KingOfTheHill,
+            Variant::ThreeCheck => &ThreeCheck,
+            Variant::Atomic => &Atomic,
+            Variant::Horde => &Horde,
+        }
+    }
+    /// Whether the variant is played by the rules of standard chess, which are the only ones UCI
+    /// engines know. Chess960 only changes the start position and castling.
+    pub fn has_standard_rules(&self) -> bool {
+        matches!(self, Variant::Standard | Variant::Chess960)
+    }
+    /// Starts a new game of the variant, a Chess960 game from a random start position.
+    pub fn create_game(&self) -> Game {
+        match self {
+            Variant::Chess960 => Game::new_chess960(get_random_position()).unwrap(),
+            Variant::Horde => game_from_variant_fen(HORDE_FEN, *self).unwrap(),
+            _ => {
+                let mut game = Game::new();
+                game.variant = *self;
+                game
+            }
+        }
+    }
+}
+
+/// The rules a variant can change. How the pieces move is the same in every variant, and the
+/// defaults are the rules of standard chess.
+pub trait Rules {
+    /// Whether the side has a king, which white doesn't have in Horde.
+    fn has_king(&self, _color: Color) -> bool {
+        true
+    }
+    /// Whether a pawn on the square may move two squares at once.
+    fn can_double_step(&self, color: Color, from: Square) -> bool {
+        match color {
+            Color::WHITE => from.row == 6,
+            Color::BLACK => from.row == 1,
+        }
+    }
+    /// Checks a move whose piece moves the way it may against the restrictions of the variant,
+    /// which in standard chess means that it must not leave the own king in check.
+    fn validate_move(&self, game: &mut Game, chess_move: Move) -> Result<(), &'static str> {
+        if !can_king_be_captured_after_move(game, chess_move).is_empty() {
+            return Err(CHECK_ERROR);
+        }
+
+        Ok(())
+    }
+    /// Called by `apply_move` after the pieces moved, for everything else the move does in the
+    /// variant. Pieces taken off the board go into the undo record so `unmake_move` puts them back.
+    fn after_move(&self, _game: &mut Game, _undo: &mut MoveUndo) {}
+    /// Whether the king of the side to move is attacked.
+    fn is_in_check(&self, game: &Game) -> bool {
+        is_king_attacked(game, game.next_to_move)
+    }
+    /// Whether the side to move is in check and can't get out of it.
+    fn is_mate(&self, game: &mut Game) -> bool {
+        self.is_in_check(game) && has_no_legal_moves(game)
+    }
+    /// Whether the side to move isn't in check but has no legal move, which is a draw.
+    fn is_stalemate(&self, game: &mut Game) -> bool {
+        !self.is_in_check(game) && has_no_legal_moves(game)
+    }
+    /// The side that won by a win condition of the variant other than mate.
+    fn get_winner(&self, _game: &Game) -> Option<Color> {
+        None
+    }
+}
+
+pub struct Standard;
+
+impl Rules for Standard {}
+
+fn has_no_legal_moves(game: &mut Game) -> bool {
+    get_legal_moves(game).is_empty()
+}
+
+#[cfg(test)]
+mod test_variant {
+    use crate::game::{
+        fen::get_fen,
+        variant::{horde::HORDE_FEN, Variant, VARIANTS},
+        Game,
+    };
+
+    #[test]
+    fn test_create_game() {
+        let game = Variant::Standard.create_game();
+        assert_eq!(game.variant, Variant::Standard);
+        assert_eq!(get_fen(&game), get_fen(&Game::new()));
+
+        let game = Variant::Chess960.create_game();
+        assert_eq!(game.variant, Variant::Chess960);
+        // the back rank changes, but the rooks around the king are the outermost ones
+        assert!(get_fen(&game).contains("/pppppppp/8/8/8/8/PPPPPPPP/"));
+        assert!(get_fen(&game).ends_with(" w KQkq - 0 1"));
+        assert_eq!(Variant::from_name("CHESS960"), Some(Variant::Chess960));
+        assert_eq!(Variant::from_name("chess960"), None);
+
+        for variant in VARIANTS {
+            assert_eq!(variant.create_game().variant, variant);
+            assert_eq!(Variant::from_name(variant.as_str()), Some(variant));
+        }
+        assert_eq!(
+            get_fen(&Variant::Horde.create_game()),
+            HORDE_FEN,
+            "white starts without a king"
+        );
+    }
+}
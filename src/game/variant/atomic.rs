@@ -0,0 +1,175 @@
+use crate::{
+    game::{
+        chess_move::{Move, Square},
+        chess_piece::{ChessPiece, Color, Piece},
+        validation::check_mate::{is_king_attacked, KING_OFFSETS},
+        variant::Rules,
+        Game, MoveUndo,
+    },
+    utils::{
+        error::{CHECK_ERROR, EXPLODE_OWN_KING_ERROR, KING_CAPTURE_ERROR},
+        is_in_bounds,
+    },
+};
+
+pub struct Atomic;
+
+impl Rules for Atomic {
+    fn validate_move(&self, game: &mut Game, chess_move: Move) -> Result<(), &'static str> {
+        let color = game.next_to_move;
+        let is_king = game
+            .piece_at(chess_move.from)
+            .is_some_and(|chess_piece| chess_piece.piece == Piece::KING);
+        // the king would blow itself up
+        if is_king && game.get_captured_piece(chess_move).is_some() {
+            return Err(KING_CAPTURE_ERROR);
+        }
+
+        let undo = game.apply_move(chess_move);
+        let result = if !has_king(game, color) {
+            Err(EXPLODE_OWN_KING_ERROR)
+        } else if self.get_winner(game).is_some() || are_kings_adjacent(game) {
+            // blowing up the other king wins even out of check, and a king next to the other
+            // one can't be captured without blowing up both
+            Ok(())
+        } else if is_king_attacked(game, color) {
+            Err(CHECK_ERROR)
+        } else {
+            Ok(())
+        };
+        game.unmake_move(&undo);
+
+        result
+    }
+    fn after_move(&self, game: &mut Game, undo: &mut MoveUndo) {
+        if undo.captured_piece.is_none() {
+            return;
+        }
+
+        // the capturing piece blows up together with everything around it but the pawns
+        let to = undo.chess_move.to;
+        game.remove_piece(to, undo);
+        for (row_offset, col_offset) in KING_OFFSETS {
+            let (row, col) = (to.row as i32 + row_offset, to.col as i32 + col_offset);
+            if !is_in_bounds(row, col) {
+                continue;
+            }
+            let square = Square::new(row as usize, col as usize);
+            if game
+                .piece_at(square)
+                .is_some_and(|chess_piece| chess_piece.piece != Piece::PAWN)
+            {
+                game.remove_piece(square, undo);
+            }
+        }
+    }
+    fn is_in_check(&self, game: &Game) -> bool {
+        self.get_winner(game).is_none()
+            && !are_kings_adjacent(game)
+            && is_king_attacked(game, game.next_to_move)
+    }
+    fn get_winner(&self, game: &Game) -> Option<Color> {
+        if !has_king(game, Color::WHITE) {
+            Some(Color::BLACK)
+        } else if !has_king(game, Color::BLACK) {
+            Some(Color::WHITE)
+        } else {
+            None
+        }
+    }
+}
+
+// a king that blew up is gone from the square it was last on
+fn has_king(game: &Game, color: Color) -> bool {
+    let king_position = match color {
+        Color::WHITE => game.king_position.white_king_position,
+        Color::BLACK => game.king_position.black_king_position,
+    };
+
+    game.piece_at(king_position)
+        == Some(ChessPiece {
+            piece: Piece::KING,
+            color,
+        })
+}
+
+fn are_kings_adjacent(game: &Game) -> bool {
+    let white = game.king_position.white_king_position;
+    let black = game.king_position.black_king_position;
+
+    white.row.abs_diff(black.row) <= 1 && white.col.abs_diff(black.col) <= 1
+}
+
+#[cfg(test)]
+mod test_atomic {
+    use crate::game::{
+        chess_move::Move,
+        fen::{game_from_variant_fen, get_fen, STARTING_FEN},
+        perft::perft,
+        variant::Variant,
+        Game, GameResult,
+    };
+
+    fn atomic_game(fen: &str) -> Game {
+        game_from_variant_fen(fen, Variant::Atomic).unwrap()
+    }
+
+    #[test]
+    fn test_perft() {
+        for (fen, expected_nodes) in [
+            (STARTING_FEN, [20, 400, 8902]),
+            (
+                "rn2kb1r/1pp1p2p/p2q1pp1/3P4/2P3b1/4PN2/PP3PPP/R2QKB1R b KQkq - 0 1",
+                [40, 1238, 45237],
+            ),
+        ] {
+            let mut game = atomic_game(fen);
+            for (depth, expected) in expected_nodes.into_iter().enumerate() {
+                assert_eq!(perft(&mut game, depth as u32 + 1), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_explosion() {
+        // the knight and the rook next to it blow up with the bishop, the pawn survives
+        let mut game = atomic_game("4k3/8/8/2rp4/3n4/8/1B6/4K3 w - - 0 1");
+        let before = game.clone();
+        game.validate_and_make_move(Move::from_uci("b2d4").unwrap())
+            .unwrap();
+        assert_eq!(get_fen(&game), "4k3/8/8/3p4/8/8/8/4K3 b - - 0 1");
+
+        let mut game = before.clone();
+        let undo = game.apply_move(Move::from_uci("b2d4").unwrap());
+        game.unmake_move(&undo);
+        assert_eq!(get_fen(&game), "4k3/8/8/2rp4/3n4/8/1B6/4K3 w - - 0 1");
+        assert_eq!(game.hash, before.hash);
+    }
+
+    #[test]
+    fn test_blowing_up_the_king() {
+        // the capture next to the king wins, although white is in check
+        let mut game = atomic_game("3qk3/3p4/8/8/8/8/8/3RK2r w - - 0 1");
+        game.validate_and_make_move(Move::from_uci("d1d7").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+        assert!(!game.previous_move.ends_with('+'));
+
+        // a capture next to the own king blows it up too
+        let mut game = atomic_game("4k3/8/8/8/8/8/3p4/3RK3 w - - 0 1");
+        assert!(game.validate_move(Move::from_uci("d1d2").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_kings() {
+        // the king can't capture
+        let mut game = atomic_game("4k3/8/8/8/8/8/4p3/4K3 w - - 0 1");
+        assert!(game.validate_move(Move::from_uci("e1e2").unwrap()).is_err());
+
+        // a king next to the other one is safe from the rook, until it steps away
+        let mut game = atomic_game("8/8/8/8/8/3k4/3K4/3r4 w - - 0 1");
+        assert!(!game.get_rules().is_in_check(&game));
+        assert!(game.validate_move(Move::from_uci("d2e1").unwrap()).is_err());
+        assert!(game.validate_move(Move::from_uci("d2c2").unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::game::{
+    chess_move::{Move, Square},
+    chess_piece::Color,
+    validation::check_mate::is_king_attacked,
+    variant::{Rules, Standard},
+    Game,
+};
+
+/// Lichess' setup, where the pawns on the fifth rank leave the b-, c-, f- and g-file.
+pub const HORDE_FEN: &str =
+    "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1";
+
+pub struct Horde;
+
+impl Rules for Horde {
+    fn has_king(&self, color: Color) -> bool {
+        color == Color::BLACK
+    }
+    fn can_double_step(&self, color: Color, from: Square) -> bool {
+        match color {
+            Color::WHITE => from.row == 6 || from.row == 7,
+            Color::BLACK => from.row == 1,
+        }
+    }
+    // white has no king to leave in check
+    fn validate_move(&self, game: &mut Game, chess_move: Move) -> Result<(), &'static str> {
+        match game.next_to_move {
+            Color::WHITE => Ok(()),
+            Color::BLACK => Standard.validate_move(game, chess_move),
+        }
+    }
+    fn is_in_check(&self, game: &Game) -> bool {
+        game.next_to_move == Color::BLACK && is_king_attacked(game, Color::BLACK)
+    }
+    fn get_winner(&self, game: &Game) -> Option<Color> {
+        let has_white_pieces = game
+            .field
+            .iter()
+            .flatten()
+            .flatten()
+            .any(|chess_piece| chess_piece.color == Color::WHITE);
+
+        match has_white_pieces {
+            true => None,
+            false => Some(Color::BLACK),
+        }
+    }
+}
+#[cfg(test)]
+mod test_horde {
+    use crate::game::{
+        chess_move::Move,
+        fen::{game_from_fen, game_from_variant_fen, get_fen},
+        perft::perft,
+        variant::{horde::HORDE_FEN, Variant},
+        GameResult,
+    };
+
+    #[test]
+    fn test_perft() {
+        for (fen, expected_nodes) in [
+            (HORDE_FEN, vec![8, 128, 1274, 23310]),
+            (
+                "4k3/pp4q1/3P2p1/8/P3PP2/PPP2r2/PPP5/PPPP4 b - - 0 1",
+                vec![30, 241, 6633],
+            ),
+        ] {
+            let mut game = game_from_variant_fen(fen, Variant::Horde).unwrap();
+            for (depth, expected) in expected_nodes.into_iter().enumerate() {
+                assert_eq!(perft(&mut game, depth as u32 + 1), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_first_rank_pawns() {
+        // a pawn on the first rank moves two squares, but can't be taken en passant after
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/1p6/8/P7 w - - 0 1", Variant::Horde).unwrap();
+        game.validate_and_make_move(Move::from_uci("a1a3").unwrap())
+            .unwrap();
+        assert_eq!(game.en_passant_square, None);
+        assert_eq!(get_fen(&game), "4k3/8/8/8/8/Pp6/8/8 b - - 0 1");
+    }
+
+    #[test]
+    fn test_black_wins_by_taking_everything() {
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/8/8/r6P b - - 0 1", Variant::Horde).unwrap();
+        game.validate_and_make_move(Move::from_uci("a1h1").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, Some(GameResult::BlackWon));
+    }
+
+    #[test]
+    fn test_white_wins_by_mate() {
+        let mut game =
+            game_from_variant_fen("6k1/5ppp/8/8/8/8/P7/R7 w - - 0 1", Variant::Horde).unwrap();
+        game.validate_and_make_move(Move::from_uci("a1b1").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, None);
+        game.validate_and_make_move(Move::from_uci("g8h8").unwrap())
+            .unwrap();
+        game.validate_and_make_move(Move::from_uci("b1b8").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+    }
+
+    #[test]
+    fn test_blocked_pawns_are_a_stalemate() {
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/p7/P7/8 b - - 0 1", Variant::Horde).unwrap();
+        game.validate_and_make_move(Move::from_uci("e8d8").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, Some(GameResult::Draw));
+    }
+
+    #[test]
+    fn test_kings() {
+        // white can't have a king, and black needs one
+        assert!(game_from_variant_fen("4k3/8/8/8/8/8/8/4K2P w - - 0 1", Variant::Horde).is_err());
+        assert!(game_from_variant_fen("8/8/8/8/8/8/8/7P w - - 0 1", Variant::Horde).is_err());
+        assert!(game_from_fen(HORDE_FEN).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use crate::game::{
+    chess_move::Square,
+    chess_piece::{ChessPiece, Color, Piece},
+    variant::Rules,
+    Game,
+};
+
+/// d4, e4, d5 and e5
+const HILL: [Square; 4] = [
+    Square { row: 3, col: 3 },
+    Square { row: 3, col: 4 },
+    Square { row: 4, col: 3 },
+    Square { row: 4, col: 4 },
+];
+
+pub struct KingOfTheHill;
+
+impl Rules for KingOfTheHill {
+    fn get_winner(&self, game: &Game) -> Option<Color> {
+        [Color::WHITE, Color::BLACK].into_iter().find(|&color| {
+            let king = Some(ChessPiece {
+                piece: Piece::KING,
+                color,
+            });
+            HILL.iter().any(|&square| game.piece_at(square) == king)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_king_of_the_hill {
+    use crate::game::{chess_move::Move, fen::game_from_variant_fen, variant::Variant, GameResult};
+
+    #[test]
+    fn test_king_on_the_hill() {
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/4K3/8/8 w - - 0 1", Variant::KingOfTheHill).unwrap();
+        game.validate_and_make_move(Move::from_uci("e3d3").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, None);
+        game.validate_and_make_move(Move::from_uci("e8e7").unwrap())
+            .unwrap();
+        game.validate_and_make_move(Move::from_uci("d3d4").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+
+        // the same move is nothing special in standard chess
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/3K4/8/8 w - - 0 1", Variant::Standard).unwrap();
+        game.validate_and_make_move(Move::from_uci("d3d4").unwrap())
+            .unwrap();
+        assert_eq!(game.game_result, None);
+    }
+}
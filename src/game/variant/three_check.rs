@@ -0,0 +1,60 @@
+use crate::game::{
+    chess_piece::Color, validation::check_mate::is_king_attacked, variant::Rules,
+    zobrist::get_checks_key, Game, MoveUndo,
+};
+
+const CHECKS_TO_WIN: u32 = 3;
+
+pub struct ThreeCheck;
+
+impl Rules for ThreeCheck {
+    fn after_move(&self, game: &mut Game, _undo: &mut MoveUndo) {
+        if !is_king_attacked(game, game.next_to_move) {
+            return;
+        }
+        game.hash ^= get_checks_key(game.checks_given);
+        match game.next_to_move {
+            Color::WHITE => game.checks_given.black += 1,
+            Color::BLACK => game.checks_given.white += 1,
+        }
+        game.hash ^= get_checks_key(game.checks_given);
+    }
+    fn get_winner(&self, game: &Game) -> Option<Color> {
+        if game.checks_given.white >= CHECKS_TO_WIN {
+            Some(Color::WHITE)
+        } else if game.checks_given.black >= CHECKS_TO_WIN {
+            Some(Color::BLACK)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_three_check {
+    use crate::game::{
+        chess_move::Move, fen::game_from_variant_fen, variant::Variant, ChecksGiven, GameResult,
+    };
+
+    #[test]
+    fn test_third_check_wins() {
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", Variant::ThreeCheck).unwrap();
+        for uci in ["a1a8", "e8e7", "a8a7", "e7e6", "a7a6"] {
+            game.validate_and_make_move(Move::from_uci(uci).unwrap())
+                .unwrap();
+        }
+        assert_eq!(game.checks_given, ChecksGiven { white: 3, black: 0 });
+        assert_eq!(game.game_result, Some(GameResult::WhiteWon));
+    }
+
+    #[test]
+    fn test_unmake_restores_checks() {
+        let mut game =
+            game_from_variant_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", Variant::ThreeCheck).unwrap();
+        let undo = game.apply_move(Move::from_uci("a1a8").unwrap());
+        assert_eq!(game.checks_given.white, 1);
+        game.unmake_move(&undo);
+        assert_eq!(game.checks_given, ChecksGiven::default());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::game::{
+    chess_move::Square,
+    chess_piece::{ChessPiece, Color, Piece},
+    ChecksGiven, Game,
+};
+
+// one key per piece, color and square, then the side to move, the four castling rights with the
+// file of their rook, the file of the en passant square and the first three checks of each side
+const KEY_COUNT: usize = 6 * 2 * 64 + 1 + 4 * 8 + 8 + 2 * 3;
+const SIDE_KEY: usize = 6 * 2 * 64;
+const CASTLING_KEYS: usize = SIDE_KEY + 1;
+const EN_PASSANT_KEYS: usize = CASTLING_KEYS + 4 * 8;
+const CHECK_KEYS: usize = EN_PASSANT_KEYS + 8;
+
+// the keys only need to look random and stay the same between runs, so they are generated at
+// compile time with splitmix64
+const KEYS: [u64; KEY_COUNT] = {
+    let mut keys = [0; KEY_COUNT];
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut i = 0;
+    while i < KEY_COUNT {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut key = state;
+        key = (key ^ (key >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        key = (key ^ (key >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        keys[i] = key ^ (key >> 31);
+        i += 1;
+    }
+    keys
+};
+
+/// Computes the hash of the position from scratch. `Game` keeps its hash up to date while moves
+/// are made, so this is only needed when a game is set up.
+pub fn get_hash(game: &Game) -> u64 {
+    let mut hash = 0;
+
+    for (row, squares) in game.field.iter().enumerate() {
+        for (col, square) in squares.iter().enumerate() {
+            if let Some(chess_piece) = square {
+                hash ^= get_piece_key(*chess_piece, Square::new(row, col));
+            }
+        }
+    }
+    if game.next_to_move == Color::BLACK {
+        hash ^= get_side_key();
+    }
+
+    hash ^ get_castling_key(game) ^ get_en_passant_key(game) ^ get_checks_key(game.checks_given)
+}
+
+pub fn get_piece_key(chess_piece: ChessPiece, square: Square) -> u64 {
+    let piece_index = match chess_piece.piece {
+        Piece::PAWN => 0,
+        Piece::KNIGHT => 1,
+        Piece::BISHOP => 2,
+        Piece::ROOK => 3,
+        Piece::QUEEN => 4,
+        Piece::KING => 5,
+    };
+    let color_index = match chess_piece.color {
+        Color::WHITE => 0,
+        Color::BLACK => 1,
+    };
+
+    KEYS[(color_index * 6 + piece_index) * 64 + square.row * 8 + square.col]
+}
+
+pub fn get_side_key() -> u64 {
+    KEYS[SIDE_KEY]
+}
+
+/// Each castling right is hashed together with the file of its rook, which only differs from the
+/// a- and h-file in Chess960.
+pub fn get_castling_key(game: &Game) -> u64 {
+    let can_castle = game.can_castle;
+    let cols = game.castling_rook_cols;
+    let mut key = 0;
+    for (i, (can_castle, col)) in [
+        (can_castle.white_can_short_castle, cols.short),
+        (can_castle.white_can_long_castle, cols.long),
+        (can_castle.black_can_short_castle, cols.short),
+        (can_castle.black_can_long_castle, cols.long),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if can_castle {
+            key ^= KEYS[CASTLING_KEYS + i * 8 + col];
+        }
+    }
+
+    key
+}
+
+/// The en passant square only counts if a pawn of the side to move stands next to the pawn that
+/// can be captured, so that positions which only differ by an unusable en passant square are
+/// still recognized as the same.
+pub fn get_en_passant_key(game: &Game) -> u64 {
+    let Some(square) = game.en_passant_square else {
+        return 0;
+    };
+
+    // the pawn that moved two squares stands one row behind the en passant square
+    let pawn_row = match game.next_to_move {
+        Color::WHITE => square.row + 1,
+        Color::BLACK => square.row - 1,
+    };
+    let capturing_pawn = Some(ChessPiece {
+        piece: Piece::PAWN,
+        color: game.next_to_move,
+    });
+    let can_capture = [square.col.wrapping_sub(1), square.col + 1]
+        .into_iter()
+        .any(|col| col < 8 && game.field[pawn_row][col] == capturing_pawn);
+
+    if can_capture {
+        KEYS[EN_PASSANT_KEYS + square.col]
+    } else {
+        0
+    }
+}
+
+/// The checks each side has given, which only count in Three-check. The game is over after the
+/// third, so there are no keys for more.
+pub fn get_checks_key(checks_given: ChecksGiven) -> u64 {
+    let mut key = 0;
+    for (i, checks) in [checks_given.white, checks_given.black]
+        .into_iter()
+        .enumerate()
+    {
+        if checks > 0 {
+            key ^= KEYS[CHECK_KEYS + i * 3 + checks.min(3) as usize - 1];
+        }
+    }
+
+    key
+}
+
+#[cfg(test)]
+mod test_zobrist {
+    use crate::game::{
+        chess_move::Move,
+        fen::{game_from_fen, game_from_variant_fen, STARTING_FEN},
+        move_gen::get_legal_moves,
+        variant::Variant,
+        zobrist::get_hash,
+        Game,
+    };
+
+    fn play(game: &mut Game, moves: &[&str]) {
+        for uci in moves {
+            game.validate_and_make_move(Move::from_uci(uci).unwrap())
+                .unwrap();
+        }
+    }
+
+    // walks the move tree and checks that the incremental hash always matches a fresh one
+    fn assert_hash_matches(game: &mut Game, depth: u32) {
+        assert_eq!(game.hash, get_hash(game));
+        if depth == 0 {
+            return;
+        }
+
+        let hash = game.hash;
+        for chess_move in get_legal_moves(game) {
+            let undo = game.apply_move(chess_move);
+            assert_hash_matches(game, depth - 1);
+            game.unmake_move(&undo);
+            assert_eq!(game.hash, hash);
+        }
+    }
+
+    #[test]
+    fn test_incremental_hash() {
+        assert_hash_matches(&mut Game::new(), 3);
+        // castling, en passant and promotions from both sides
+        let mut game =
+            game_from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        assert_hash_matches(&mut game, 2);
+        let mut game =
+            game_from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8").unwrap();
+        assert_hash_matches(&mut game, 2);
+        // Chess960 castling where the king takes the rook next to it
+        let mut game = game_from_fen("rk4r1/pppppppp/8/8/8/8/PPPPPPPP/RK4R1 w KQkq - 0 1").unwrap();
+        assert_hash_matches(&mut game, 3);
+        // Atomic explosions that also blow up rooks that could castle
+        let mut game = game_from_variant_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            Variant::Atomic,
+        )
+        .unwrap();
+        assert_hash_matches(&mut game, 2);
+        // checks that count in Three-check
+        let mut game = game_from_variant_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            Variant::ThreeCheck,
+        )
+        .unwrap();
+        assert_hash_matches(&mut game, 2);
+    }
+
+    #[test]
+    fn test_transposition() {
+        let mut game = Game::new();
+        play(&mut game, &["g1f3", "g8f6", "b1c3", "b8c6"]);
+        let mut other_game = Game::new();
+        play(&mut other_game, &["b1c3", "b8c6", "g1f3", "g8f6"]);
+        assert_eq!(game.hash, other_game.hash);
+
+        // moving the knights back and forth repeats the starting position
+        play(&mut game, &["f3g1", "f6g8", "c3b1", "c6b8"]);
+        assert_eq!(game.hash, Game::new().hash);
+        assert_eq!(game.hash, game_from_fen(STARTING_FEN).unwrap().hash);
+    }
+
+    #[test]
+    fn test_side_castling_and_en_passant_change_hash() {
+        let white = game_from_fen("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+        let black = game_from_fen("4k3/8/8/8/8/8/8/R3K3 b Q - 0 1").unwrap();
+        let no_castling = game_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        assert_ne!(white.hash, black.hash);
+        assert_ne!(white.hash, no_castling.hash);
+
+        // only an en passant square that can be used changes the position
+        let usable = game_from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let without = game_from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_ne!(usable.hash, without.hash);
+        let unusable = game_from_fen("4k3/8/8/3p3P/8/8/8/4K3 w - d6 0 1").unwrap();
+        let without = game_from_fen("4k3/8/8/3p3P/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(unusable.hash, without.hash);
+    }
+
+    #[test]
+    fn test_checks_and_rook_files_change_hash() {
+        // the rook checks and goes back, which repeats the position in standard chess only
+        let fen = "4k3/8/8/8/8/8/8/R3K3 w - - 0 1";
+        for (variant, repeats) in [(Variant::Standard, true), (Variant::ThreeCheck, false)] {
+            let mut game = game_from_variant_fen(fen, variant).unwrap();
+            play(&mut game, &["a1a8", "e8e7", "a8a1", "e7e8"]);
+            assert_eq!(game.hash, get_hash(&game));
+            assert_eq!(
+                game.hash == game_from_variant_fen(fen, variant).unwrap().hash,
+                repeats
+            );
+        }
+
+        // the same board, but castling long goes with the rook on a1 or the one on b1
+        let a_file = game_from_fen("4k3/8/8/8/8/8/8/RR2K3 w Q - 0 1").unwrap();
+        let b_file = game_from_fen("4k3/8/8/8/8/8/8/RR2K3 w B - 0 1").unwrap();
+        assert_ne!(a_file.hash, b_file.hash);
+    }
+}
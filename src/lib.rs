@@ -1,3 +1,6 @@
 pub mod db;
+pub mod engine;
 pub mod game;
+pub mod time_control;
 pub mod utils;
+pub mod voting;
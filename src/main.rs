@@ -1,26 +1,200 @@
-use std::{env, sync::Mutex};
+use std::{env, sync::Mutex, time::Duration};
 
-use actix_web::{get, post, web, App, HttpResponse, HttpServer, Responder, Result};
+use actix_web::{get, post, web, App, HttpRequest, HttpResponse, HttpServer, Responder, Result};
 use chess_voting::{
-    db::{Move, Vote, DB},
-    game::{chess_piece::Color, Game, GameResult},
+    db::{EngineAgreement, GameRecord, GameSummary, Move, TurnVotes, Turnout, Vote, DB},
+    engine::{search, uci::UciEngine, EngineOpponent, SearchLimits, SearchResult, Strength},
+    game::{
+        action::GameAction,
+        chess_move,
+        chess_piece::Color,
+        fen::{game_from_variant_fen, get_fen},
+        move_gen::{find_move_by_notation, replay_moves},
+        opening::{classify_opening, Opening},
+        variant::Variant,
+        Game, GameResult,
+    },
+    time_control::TimeControl,
     utils::{
-        request::{FinishRequest, MoveRequest},
-        response::serialize_field,
+        error::{
+            ALREADY_VOTED_ERROR, EMPTY_BALLOT_ERROR, ENGINE_SEARCH_ERROR, INVALID_HISTORY_ERROR,
+            NOT_TEAMS_TURN_ERROR, NO_MOVE_TO_TAKE_BACK_ERROR, NO_TEAMS_ERROR, OTHER_TEAM_ERROR,
+            OUT_OF_TIME_ERROR, PLY_OUT_OF_RANGE_ERROR, TEAMS_WITH_ENGINE_ERROR, UNKNOWN_TEAM_ERROR,
+            VOTING_CLOSED_ERROR,
+        },
+        request::{
+            ActionRequest, BallotEntry, FinishRequest, JoinTeamRequest, MoveRequest, PageQuery,
+            PositionQuery, TurnoutQuery, VoteRequest,
+        },
+        response::{
+            serialize_field, serialize_search_result, AnalysisResponse, CandidateAnalysis,
+            ClockResponse, GameDetailResponse, GameListResponse, GameStatusResponse,
+            PositionResponse, SearchResponse, TallyResponse, TeamResponse,
+        },
+        voter::{get_voter_address, get_voter_hash},
     },
+    voting::{tally, Teams, VotingMethod},
 };
 use dotenv::dotenv;
 use log::{error, info};
+use uuid::Uuid;
+
+const HINT_STRENGTH: Strength = Strength::HARD;
+const ANALYSIS_STRENGTH: Strength = Strength::MEDIUM;
+// how often the clock of the side to move is checked, so a game is lost on time even if nobody
+// votes or moves anymore
+const CLOCK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
 
 struct Server {
     pub game: Mutex<Game>,
+    // held by the handlers that change the current game until they are done, so e.g. a move can't
+    // be played between the DB calls of a takeback. The game itself is locked only briefly
+    pub game_update: tokio::sync::Mutex<()>,
     pub db: DB,
+    // the built-in engine, if the community plays against it. New games that are started
+    // automatically keep the opponent of the previous game
+    pub opponent: Mutex<Option<EngineOpponent>>,
+    // the two teams if the game is played by two communities instead of one, kept for new games
+    // like the opponent
+    pub teams: Mutex<Option<Teams>>,
+    // the time control of the current game, kept for new games like the opponent
+    pub time_control: Mutex<Option<TimeControl>>,
+    // an external engine that is used instead of the built-in one if configured
+    pub uci_engine: Option<Mutex<UciEngine>>,
+    // how the moves of the current game are voted for, new games that are started automatically
+    // keep it like the opponent
+    pub voting_method: Mutex<VotingMethod>,
+    // the variant of the current game, kept for new games like the opponent
+    pub variant: Mutex<Variant>,
+    // salts the hashed addresses of the vote log
+    pub voter_salt: String,
+    // whether the voter's address is taken from the proxy headers instead of the connection
+    pub trust_proxy_headers: bool,
+    // the UTC time of day like 00:00 at which the voting for a move closes in new games
+    pub voting_close_time: String,
 }
 impl Server {
     pub async fn new() -> Server {
+        let color = match env::var("ENGINE_COLOR").as_deref() {
+            Ok("WHITE") => Some(Color::WHITE),
+            Ok("BLACK") => Some(Color::BLACK),
+            Ok(color) => panic!("ENGINE_COLOR needs to be WHITE or BLACK, got {}", color),
+            Err(_) => None,
+        };
+        let strength = match env::var("ENGINE_STRENGTH").as_deref() {
+            Ok("EASY") => Strength::EASY,
+            Ok("MEDIUM") | Err(_) => Strength::MEDIUM,
+            Ok("HARD") => Strength::HARD,
+            Ok("MAXIMUM") => Strength::MAXIMUM,
+            Ok(strength) => panic!(
+                "ENGINE_STRENGTH needs to be EASY, MEDIUM, HARD or MAXIMUM, got {}",
+                strength
+            ),
+        };
+        let opponent = color.map(|color| EngineOpponent { color, strength });
+        let teams = match (env::var("TEAM_WHITE"), env::var("TEAM_BLACK")) {
+            (Ok(white), Ok(black)) => Some(Teams { white, black }),
+            (Err(_), Err(_)) => None,
+            _ => panic!("TEAM_WHITE and TEAM_BLACK need to be set together"),
+        };
+        if let Some(teams) = &teams {
+            if let Err(e) = teams.validate() {
+                panic!("{}", e);
+            }
+            if opponent.is_some() {
+                panic!("{}", TEAMS_WITH_ENGINE_ERROR);
+            }
+        }
+        let uci_engine = env::var("UCI_ENGINE").ok().map(|path| {
+            let uci_engine = UciEngine::start(&path, &[]).expect("Could not start UCI engine");
+            info!(
+                "Using UCI engine {}",
+                uci_engine.name.as_deref().unwrap_or(&path)
+            );
+            Mutex::new(uci_engine)
+        });
+
+        // without a fixed salt the voters can't be recognized across restarts
+        let voter_salt = env::var("VOTER_SALT").unwrap_or_else(|_| Uuid::new_v4().to_string());
+        let trust_proxy_headers = match env::var("TRUST_PROXY_HEADERS").as_deref() {
+            Ok("true") => true,
+            Ok("false") | Err(_) => false,
+            Ok(value) => panic!(
+                "TRUST_PROXY_HEADERS needs to be true or false, got {}",
+                value
+            ),
+        };
+
+        let voting_method = match env::var("VOTING_METHOD").as_deref() {
+            Ok("PLURALITY") | Err(_) => VotingMethod::PLURALITY,
+            Ok("RANKED") => VotingMethod::RANKED,
+            Ok("APPROVAL") => VotingMethod::APPROVAL,
+            Ok(voting_method) => panic!(
+                "VOTING_METHOD needs to be PLURALITY, RANKED or APPROVAL, got {}",
+                voting_method
+            ),
+        };
+        let get_seconds = |name: &str| {
+            env::var(name).ok().map(|seconds| {
+                seconds
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| panic!("{} needs to be in seconds, got {}", name, seconds))
+            })
+        };
+        let time_control = match (get_seconds("TIME_PER_MOVE"), get_seconds("TIME_TOTAL")) {
+            (None, None) => None,
+            (seconds_per_move, total_seconds) => Some(TimeControl {
+                seconds_per_move,
+                total_seconds,
+                increment_seconds: get_seconds("TIME_INCREMENT").unwrap_or(0),
+            }),
+        };
+        if let Some(Err(e)) = time_control.map(|time_control| time_control.validate()) {
+            panic!("{}", e);
+        }
+        let variant = match env::var("VARIANT").as_deref() {
+            Ok(name) => Variant::from_name(name).unwrap_or_else(|| {
+                panic!(
+                    "VARIANT needs to be STANDARD, CHESS960, KING_OF_THE_HILL, THREE_CHECK, \
+                     ATOMIC or HORDE, got {}",
+                    name
+                )
+            }),
+            Err(_) => Variant::Standard,
+        };
+        let voting_close_time = env::var("VOTING_CLOSE_TIME").unwrap_or("00:00".to_string());
+        if !is_valid_time(&voting_close_time) {
+            panic!(
+                "VOTING_CLOSE_TIME needs to be a time like 00:00, got {}",
+                voting_close_time
+            );
+        }
+
+        let game = variant.create_game();
+        let db = DB::new().await;
+        db.create_game(
+            &game,
+            opponent.as_ref(),
+            teams.as_ref(),
+            time_control.as_ref(),
+            voting_method,
+            &voting_close_time,
+        )
+        .await;
+
         Server {
-            game: Mutex::new(Game::new()),
-            db: DB::new().await,
+            game: Mutex::new(game),
+            game_update: tokio::sync::Mutex::new(()),
+            db,
+            opponent: Mutex::new(opponent),
+            teams: Mutex::new(teams),
+            time_control: Mutex::new(time_control),
+            voting_method: Mutex::new(voting_method),
+            variant: Mutex::new(variant),
+            uci_engine,
+            voter_salt,
+            trust_proxy_headers,
+            voting_close_time,
         }
     }
 }
@@ -34,17 +208,43 @@ async fn main() -> std::io::Result<()> {
     info!("Server listening on port {}", port);
 
     let server = web::Data::new(Server::new().await);
+    let clock_server = server.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(CLOCK_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let _game_update = clock_server.game_update.lock().await;
+            if check_clock(&clock_server).await {
+                play_engine_move(&clock_server).await;
+            }
+        }
+    });
     HttpServer::new(move || {
         App::new()
             .app_data(server.clone())
             .service(health)
             .service(get_game_history)
             .service(get_game_state)
+            .service(get_game_status)
+            .service(get_game_info)
+            .service(get_games)
+            .service(get_archived_game)
+            .service(get_archived_position)
+            .service(get_turn_votes)
+            .service(get_turnout)
+            .service(get_engine_agreement)
+            .service(get_hint)
+            .service(get_analysis)
             .service(get_votes)
             .service(finish_game)
             .service(validate_move)
             .service(make_vote)
+            .service(get_tally)
+            .service(get_teams)
+            .service(join_team)
             .service(make_move)
+            .service(make_action)
+            .service(take_back_move)
     })
     .bind((url, port.parse::<u16>().unwrap()))?
     .run()
@@ -59,8 +259,8 @@ async fn health() -> HttpResponse {
 #[get("/game/history")]
 async fn get_game_history(server: web::Data<Server>) -> Result<impl Responder> {
     info!("Checking game history...");
-    let game = server.game.lock().unwrap();
-    let moves: Vec<Move> = server.db.get_moves(&game.id.to_string()).await;
+    let game_id = server.game.lock().unwrap().id.to_string();
+    let moves: Vec<Move> = server.db.get_moves(&game_id).await;
     info!("Fetched moves from history, got {} moves", moves.len());
     Ok(web::Json(moves))
 }
@@ -74,6 +274,240 @@ async fn get_game_state(server: web::Data<Server>) -> Result<impl Responder> {
     Ok(web::Json(state))
 }
 
+// everything about the current game that the board alone doesn't show
+#[get("/game/status")]
+async fn get_game_status(server: web::Data<Server>) -> Result<impl Responder> {
+    info!("Checking game status...");
+    let (game_id, variant, draw_offer, next_to_move, checks_given) = {
+        let game = server.game.lock().unwrap();
+        (
+            game.id.to_string(),
+            game.variant,
+            game.draw_offer,
+            game.next_to_move,
+            game.checks_given,
+        )
+    };
+    let moves: Vec<Move> = server.db.get_moves(&game_id).await;
+    let opening = get_opening(variant, &moves);
+    let closes_at = server
+        .db
+        .get_game(&game_id)
+        .await
+        .and_then(|game| game.voting_closes_at);
+    let clock = server
+        .db
+        .get_clock(&game_id)
+        .await
+        .map(|clock| ClockResponse {
+            white_time_left: clock.get_time_left(Color::WHITE, next_to_move),
+            black_time_left: clock.get_time_left(Color::BLACK, next_to_move),
+            move_time_left: clock.get_move_time_left(next_to_move),
+        });
+    info!("Fetched the game status");
+    Ok(web::Json(GameStatusResponse {
+        opening,
+        closes_at,
+        draw_offer,
+        clock,
+        checks_given: (variant == Variant::ThreeCheck).then_some(checks_given),
+    }))
+}
+
+#[get("/game/info")]
+async fn get_game_info(server: web::Data<Server>) -> HttpResponse {
+    info!("Checking game info...");
+    let game_id = server.game.lock().unwrap().id.to_string();
+    let game: Option<GameRecord> = server.db.get_game(&game_id).await;
+    match game {
+        Some(game) => HttpResponse::Ok().json(game),
+        None => HttpResponse::NotFound().body("Game not found".to_string()),
+    }
+}
+
+#[get("/games")]
+async fn get_games(query: web::Query<PageQuery>, server: web::Data<Server>) -> HttpResponse {
+    info!("Fetching games...");
+    let (page, page_size) = (query.get_page(), query.get_page_size());
+    let games: Vec<GameSummary> = server.db.get_games(page_size, (page - 1) * page_size).await;
+    let total = server.db.count_games().await;
+    info!("Fetched {} of {} games", games.len(), total);
+    HttpResponse::Ok().json(GameListResponse {
+        games,
+        page,
+        page_size,
+        total,
+    })
+}
+
+#[get("/games/{id}")]
+async fn get_archived_game(path: web::Path<String>, server: web::Data<Server>) -> HttpResponse {
+    let game_id = path.into_inner();
+    info!("Fetching game {}...", &game_id);
+    let Some(game_record) = server.db.get_game(&game_id).await else {
+        return HttpResponse::NotFound().body("Game not found".to_string());
+    };
+    let moves: Vec<Move> = server.db.get_moves(&game_id).await;
+
+    let game = match get_chess_moves(&moves)
+        .and_then(|chess_moves| replay_game(&game_record, &chess_moves))
+    {
+        Ok(game) => game,
+        Err(e) => {
+            error!("Could not replay game {}: {}", &game_id, e);
+            return HttpResponse::InternalServerError().body(e);
+        }
+    };
+    info!("Fetched game {} with {} moves", &game_id, moves.len());
+    HttpResponse::Ok().json(GameDetailResponse {
+        game: game_record,
+        moves,
+        field: serialize_field(&game.field),
+        fen: get_fen(&game),
+    })
+}
+
+#[get("/games/{id}/position")]
+async fn get_archived_position(
+    path: web::Path<String>,
+    query: web::Query<PositionQuery>,
+    server: web::Data<Server>,
+) -> HttpResponse {
+    let game_id = path.into_inner();
+    let ply = query.ply;
+    info!("Fetching position at ply {} of game {}...", ply, &game_id);
+    let Some(game_record) = server.db.get_game(&game_id).await else {
+        return HttpResponse::NotFound().body("Game not found".to_string());
+    };
+    let mut moves: Vec<Move> = server.db.get_moves(&game_id).await;
+    if ply > moves.len() {
+        error!("Could not fetch position: {}", PLY_OUT_OF_RANGE_ERROR);
+        return HttpResponse::BadRequest().body(PLY_OUT_OF_RANGE_ERROR);
+    }
+    moves.truncate(ply);
+
+    let game = match get_chess_moves(&moves)
+        .and_then(|chess_moves| replay_game(&game_record, &chess_moves))
+    {
+        Ok(game) => game,
+        Err(e) => {
+            error!("Could not replay game {}: {}", &game_id, e);
+            return HttpResponse::InternalServerError().body(e);
+        }
+    };
+    info!("Fetched position at ply {} of game {}", ply, &game_id);
+    HttpResponse::Ok().json(PositionResponse {
+        ply,
+        field: serialize_field(&game.field),
+        fen: get_fen(&game),
+        last_move: moves.pop(),
+    })
+}
+
+#[get("/games/{id}/votes")]
+async fn get_turn_votes(path: web::Path<String>, server: web::Data<Server>) -> HttpResponse {
+    let game_id = path.into_inner();
+    info!("Fetching votes of game {}...", &game_id);
+    if server.db.get_game(&game_id).await.is_none() {
+        return HttpResponse::NotFound().body("Game not found".to_string());
+    }
+    let turn_votes: Vec<TurnVotes> = server.db.get_turn_votes(&game_id).await;
+    info!("Fetched votes of {} turns", turn_votes.len());
+    HttpResponse::Ok().json(turn_votes)
+}
+
+#[get("/stats/turnout")]
+async fn get_turnout(query: web::Query<TurnoutQuery>, server: web::Data<Server>) -> HttpResponse {
+    info!("Fetching turnout...");
+    let turnout: Vec<Turnout> = server
+        .db
+        .get_turnout(query.interval.get_period_format())
+        .await;
+    info!("Fetched turnout of {} periods", turnout.len());
+    HttpResponse::Ok().json(turnout)
+}
+
+#[get("/stats/engine_agreement")]
+async fn get_engine_agreement(server: web::Data<Server>) -> HttpResponse {
+    info!("Fetching engine agreement...");
+    let agreement: EngineAgreement = server.db.get_engine_agreement().await;
+    info!(
+        "The community played {} of {} engine moves",
+        agreement.engine_moves, agreement.community_moves
+    );
+    HttpResponse::Ok().json(agreement)
+}
+
+#[get("/game/hint")]
+async fn get_hint(server: web::Data<Server>) -> HttpResponse {
+    info!("Searching a hint...");
+    let game = server.game.lock().unwrap().clone();
+    // without randomness, so that the moves and scores are the engine's real ones
+    let limits = SearchLimits {
+        randomness: 0,
+        ..HINT_STRENGTH.get_search_limits()
+    };
+    match search_position(&server, game, limits).await {
+        Ok(result) => {
+            info!("Found hint {:?}", result.best_move);
+            let hint: SearchResponse = serialize_search_result(&result);
+            HttpResponse::Ok().json(hint)
+        }
+        Err(e) => {
+            error!("Could not search a hint: {}", e);
+            HttpResponse::InternalServerError().body(e)
+        }
+    }
+}
+
+#[get("/game/analysis")]
+async fn get_analysis(server: web::Data<Server>) -> HttpResponse {
+    info!("Analysing position and voted moves...");
+    let mut game = server.game.lock().unwrap().clone();
+    let votes: Vec<Vote> = server.db.get_votes().await;
+    // without randomness, so that the moves and scores are the engine's real ones
+    let limits = SearchLimits {
+        randomness: 0,
+        ..ANALYSIS_STRENGTH.get_search_limits()
+    };
+
+    let position = match search_position(&server, game.clone(), limits).await {
+        Ok(position) => position,
+        Err(e) => {
+            error!("Could not analyse the position: {}", e);
+            return HttpResponse::InternalServerError().body(e);
+        }
+    };
+
+    // every candidate is searched from the opponent's side after playing it, so its score is
+    // negated to compare it with the score of the position
+    let mut candidates = vec![];
+    for vote in votes {
+        let mut score = None;
+        if let Some(chess_move) = find_move_by_notation(&mut game, &vote.move_notation) {
+            let mut candidate_game = game.clone();
+            candidate_game.make_move(chess_move);
+            match search_position(&server, candidate_game, limits).await {
+                Ok(result) => score = Some(-result.score),
+                Err(e) => error!("Could not analyse {}: {}", vote.move_notation, e),
+            }
+        }
+        candidates.push(CandidateAnalysis {
+            move_notation: vote.move_notation,
+            votes: vote.votes,
+            score,
+            score_loss: score.map(|score| (position.score - score).max(0)),
+        });
+    }
+    info!("Analysed {} voted moves", candidates.len());
+
+    let analysis = AnalysisResponse {
+        position: serialize_search_result(&position),
+        candidates,
+    };
+    HttpResponse::Ok().json(analysis)
+}
+
 #[get("/game/current_votes")]
 async fn get_votes(server: web::Data<Server>) -> Result<impl Responder> {
     info!("Checking game votes...");
@@ -85,20 +519,54 @@ async fn get_votes(server: web::Data<Server>) -> Result<impl Responder> {
 #[post("/game/finish")]
 async fn finish_game(req: web::Json<FinishRequest>, server: web::Data<Server>) -> HttpResponse {
     info!("Finishing game...");
-    let mut game = server.game.lock().unwrap();
-    *game = Game::new();
-    server.db.finish_game(&req.game_result, &req.game_id).await;
+    let _game_update = server.game_update.lock().await;
+    if let Some(teams) = &req.teams {
+        let validation = match req.opponent {
+            Some(_) => Err(TEAMS_WITH_ENGINE_ERROR),
+            None => teams.validate(),
+        };
+        if let Err(e) = validation {
+            error!("Could not set up the teams: {}", e);
+            return HttpResponse::BadRequest().body(e);
+        }
+    }
+    if let Some(Err(e)) = req.time_control.map(|time_control| time_control.validate()) {
+        error!("Could not set up the time control: {}", e);
+        return HttpResponse::BadRequest().body(e);
+    }
+    let new_game = req.variant.create_game();
+    *server.game.lock().unwrap() = new_game.clone();
+    *server.opponent.lock().unwrap() = req.opponent;
+    *server.time_control.lock().unwrap() = req.time_control;
+    *server.teams.lock().unwrap() = req.teams.clone();
+    *server.voting_method.lock().unwrap() = req.voting_method;
+    *server.variant.lock().unwrap() = req.variant;
+    server
+        .db
+        .finish_game(&req.game_result, "MANUAL", &req.game_id)
+        .await;
     info!("Finished DB game");
-    server.db.create_game(&game.id.to_string()).await;
+    server
+        .db
+        .create_game(
+            &new_game,
+            req.opponent.as_ref(),
+            req.teams.as_ref(),
+            req.time_control.as_ref(),
+            req.voting_method,
+            &server.voting_close_time,
+        )
+        .await;
     info!("Created new DB game");
+    play_engine_move(&server).await;
     HttpResponse::Ok().body("OK".to_string())
 }
 
 #[post("/game/validate")]
 async fn validate_move(req: web::Json<MoveRequest>, server: web::Data<Server>) -> HttpResponse {
     info!("Validating move...");
-    let game = server.game.lock().unwrap();
-    if let Err(e) = game.validate_move(&req.from, &req.to, req.promotion) {
+    let mut game = server.game.lock().unwrap();
+    if let Err(e) = game.validate_move(req.get_move()) {
         error!("Not a valid move: {}", e);
         return HttpResponse::BadRequest().body(e);
     }
@@ -107,62 +575,583 @@ async fn validate_move(req: web::Json<MoveRequest>, server: web::Data<Server>) -
 }
 
 #[post("/game/vote")]
-async fn make_vote(req: web::Json<MoveRequest>, server: web::Data<Server>) -> HttpResponse {
+async fn make_vote(
+    req: web::Json<VoteRequest>,
+    http_req: HttpRequest,
+    server: web::Data<Server>,
+) -> HttpResponse {
     info!("Voting for a move...");
-    let game = server.game.lock().unwrap();
+    // held until the vote is counted, so that it can't end up in the tally of the next move
+    let _game_update = server.game_update.lock().await;
+    if check_clock(&server).await {
+        play_engine_move(&server).await;
+        error!("Could not vote: {}", OUT_OF_TIME_ERROR);
+        return HttpResponse::BadRequest().body(OUT_OF_TIME_ERROR);
+    }
+
+    let voter_hash = get_request_voter_hash(&server, &http_req);
+    let (game_id, turn, next_to_move, ballot) = {
+        let game = server.game.lock().unwrap();
+        let mut ballot: Vec<String> = vec![];
+        for entry in req.get_entries() {
+            let notation = match entry {
+                BallotEntry::ACTION { action } => match game.validate_action(*action) {
+                    Ok(()) => action.as_str().to_string(),
+                    Err(e) => {
+                        error!("Not a valid action: {}", e);
+                        return HttpResponse::BadRequest().body(e);
+                    }
+                },
+                BallotEntry::MOVE(move_request) => {
+                    // kinda hacky to create new game just to get the move notation, wouldve been
+                    // better to just create a get_notation method but it would have been quite
+                    // annoying to factor in all the possible cases
+                    let mut game_clone = game.clone();
+                    if let Err(e) = game_clone.validate_and_make_move(move_request.get_move()) {
+                        error!("Not a valid move: {}", e);
+                        return HttpResponse::BadRequest().body(e);
+                    }
+                    game_clone.previous_move
+                }
+            };
+            // a move that is ranked twice only counts at its best rank
+            if !ballot.contains(&notation) {
+                ballot.push(notation);
+            }
+        }
+        if ballot.is_empty() {
+            error!("Not a valid ballot: {}", EMPTY_BALLOT_ERROR);
+            return HttpResponse::BadRequest().body(EMPTY_BALLOT_ERROR);
+        }
+        // the turn is counted like in the Move table, where it increases with white's moves
+        let turn = match game.next_to_move {
+            Color::WHITE => game.turn_number + 1,
+            Color::BLACK => game.turn_number,
+        };
+        (game.id.to_string(), turn, game.next_to_move, ballot)
+    };
+    let teams = server.teams.lock().unwrap().clone();
+    if let Some(teams) = teams {
+        let voter_teams = server.db.get_voter_teams(&voter_hash).await;
+        if !teams.can_vote(&voter_teams, next_to_move) {
+            error!("Could not vote: {}", NOT_TEAMS_TURN_ERROR);
+            return HttpResponse::BadRequest().body(NOT_TEAMS_TURN_ERROR);
+        }
+    }
+    if !server.db.is_voting_open(&game_id).await {
+        error!("Could not vote: {}", VOTING_CLOSED_ERROR);
+        return HttpResponse::BadRequest().body(VOTING_CLOSED_ERROR);
+    }
+    // every voter gets a single ballot per move
+    if !server.db.add_voter(&voter_hash).await {
+        error!("Could not vote: {}", ALREADY_VOTED_ERROR);
+        return HttpResponse::BadRequest().body(ALREADY_VOTED_ERROR);
+    }
+    let voting_method = *server.voting_method.lock().unwrap();
+    for move_notation in voting_method.get_counted_moves(&ballot) {
+        server.db.vote(move_notation).await;
+    }
+    server.db.add_ballot(&ballot).await;
+
+    let player = match next_to_move {
+        Color::WHITE => "WHITE",
+        Color::BLACK => "BLACK",
+    };
+    server
+        .db
+        .log_vote(&voter_hash, &game_id, turn, player, &ballot)
+        .await;
+    info!("Voted for {}", ballot.join(", "));
+    HttpResponse::Ok().body("OK".to_string())
+}
+
+#[get("/game/tally")]
+async fn get_tally(server: web::Data<Server>) -> HttpResponse {
+    info!("Tallying ballots...");
+    let voting_method = *server.voting_method.lock().unwrap();
+    let ballots = server.db.get_ballots().await;
+    let tally = tally(voting_method, &ballots);
+    info!(
+        "Tallied {} ballots with {}, the winner is {:?}",
+        ballots.len(),
+        voting_method.as_str(),
+        tally.winner
+    );
+    HttpResponse::Ok().json(TallyResponse {
+        voting_method,
+        tally,
+    })
+}
+
+#[get("/game/teams")]
+async fn get_teams(server: web::Data<Server>) -> HttpResponse {
+    info!("Fetching teams...");
+    let Some(teams) = server.teams.lock().unwrap().clone() else {
+        return HttpResponse::NotFound().body(NO_TEAMS_ERROR);
+    };
+    let mut team_responses: Vec<TeamResponse> = vec![];
+    for color in [Color::WHITE, Color::BLACK] {
+        let name = teams.get_name(color).to_string();
+        let members = server.db.count_team_members(&name).await;
+        team_responses.push(TeamResponse {
+            color,
+            name,
+            members,
+        });
+    }
+    info!("Fetched teams");
+    HttpResponse::Ok().json(team_responses)
+}
+
+#[post("/game/team")]
+async fn join_team(
+    req: web::Json<JoinTeamRequest>,
+    http_req: HttpRequest,
+    server: web::Data<Server>,
+) -> HttpResponse {
+    info!("Joining team...");
+    let Some(teams) = server.teams.lock().unwrap().clone() else {
+        error!("Could not join team: {}", NO_TEAMS_ERROR);
+        return HttpResponse::BadRequest().body(NO_TEAMS_ERROR);
+    };
+    let Some(color) = teams.get_color(&req.team) else {
+        error!("Could not join team: {}", UNKNOWN_TEAM_ERROR);
+        return HttpResponse::BadRequest().body(UNKNOWN_TEAM_ERROR);
+    };
+    // otherwise a voter could decide the moves of both sides
+    let voter_hash = get_request_voter_hash(&server, &http_req);
+    let voter_teams = server.db.get_voter_teams(&voter_hash).await;
+    let other_team = match color {
+        Color::WHITE => &teams.black,
+        Color::BLACK => &teams.white,
+    };
+    if voter_teams.contains(other_team) {
+        error!("Could not join team: {}", OTHER_TEAM_ERROR);
+        return HttpResponse::BadRequest().body(OTHER_TEAM_ERROR);
+    }
+    server.db.join_team(&voter_hash, &req.team).await;
+    info!("Joined team {}", req.team);
+    HttpResponse::Ok().body("OK".to_string())
+}
+
+#[post("/game/action")]
+async fn make_action(req: web::Json<ActionRequest>, server: web::Data<Server>) -> HttpResponse {
+    info!("Performing action...");
+    let _game_update = server.game_update.lock().await;
+    if check_clock(&server).await {
+        play_engine_move(&server).await;
+        error!("Not a valid action: {}", OUT_OF_TIME_ERROR);
+        return HttpResponse::BadRequest().body(OUT_OF_TIME_ERROR);
+    }
+    if let Err(e) = play_action(&server, req.action).await {
+        error!("Not a valid action: {}", e);
+        return HttpResponse::BadRequest().body(e);
+    }
+    // the engine answers a draw offer on its next turn and plays the first move of a new game
+    play_engine_move(&server).await;
 
-    // kinda hacky to create new game just to get the move notation, wouldve been better to just
-    // create a get_notation method but it would have been quite annoying to factor in all the
-    // possible cases
-    let mut game_clone = game.clone();
-    game_clone.make_move(&req.from, &req.to, req.promotion);
-    server.db.vote(&game_clone.previous_move).await;
-    info!("Voted for move {}", &game_clone.previous_move);
     HttpResponse::Ok().body("OK".to_string())
 }
 
 #[post("/game/move")]
 async fn make_move(req: web::Json<MoveRequest>, server: web::Data<Server>) -> HttpResponse {
     info!("Performing move...");
-    let mut game = server.game.lock().unwrap();
-    if let Err(e) = game.validate_and_make_move(&req.from, &req.to, req.promotion) {
-        error!("Not a valid move: {}", e);
-        return HttpResponse::BadRequest().body(e);
+    let _game_update = server.game_update.lock().await;
+    if check_clock(&server).await {
+        play_engine_move(&server).await;
+        error!("Not a valid move: {}", OUT_OF_TIME_ERROR);
+        return HttpResponse::BadRequest().body(OUT_OF_TIME_ERROR);
+    }
+    let game = server.game.lock().unwrap().clone();
+    let move_id = match play_move(&server, req.get_move()).await {
+        Ok(move_id) => move_id,
+        Err(e) => {
+            error!("Not a valid move: {}", e);
+            return HttpResponse::BadRequest().body(e);
+        }
+    };
+    // the engine's choice is searched in the background, so the move isn't held up by it
+    let agreement_server = server.clone();
+    actix_web::rt::spawn(async move {
+        record_engine_move(&agreement_server, game, move_id).await;
+    });
+    play_engine_move(&server).await;
+
+    HttpResponse::Ok().body("OK".to_string())
+}
+
+#[post("/game/takeback")]
+async fn take_back_move(server: web::Data<Server>) -> HttpResponse {
+    info!("Taking back the last move...");
+    let _game_update = server.game_update.lock().await;
+    let game_id = server.game.lock().unwrap().id;
+    let Some(game_record) = server.db.get_game(&game_id.to_string()).await else {
+        return HttpResponse::NotFound().body("Game not found".to_string());
+    };
+    let moves: Vec<Move> = server.db.get_moves(&game_id.to_string()).await;
+    let mut chess_moves = match get_chess_moves(&moves) {
+        Ok(chess_moves) => chess_moves,
+        Err(e) => {
+            error!("Could not take back a move: {}", e);
+            return HttpResponse::InternalServerError().body(e);
+        }
+    };
+    if chess_moves.pop().is_none() {
+        error!("Could not take back a move: {}", NO_MOVE_TO_TAKE_BACK_ERROR);
+        return HttpResponse::BadRequest().body(NO_MOVE_TO_TAKE_BACK_ERROR);
     }
-    info!("Move {} is valid", &game.previous_move);
+    let mut taken_back = 1;
 
-    let player_str: &str;
-    match game.next_to_move {
-        Color::WHITE => player_str = "BLACK",
-        Color::BLACK => player_str = "WHITE",
+    // the engine replies right away, so its reply is taken back together with the community's
+    // move before it. Otherwise the engine would be to move without ever getting to reply
+    let opponent = *server.opponent.lock().unwrap();
+    let engine_to_move = |chess_moves: &[chess_move::Move]| {
+        let black_to_move = chess_moves.len() % 2 == 1;
+        match opponent {
+            Some(EngineOpponent { color, .. }) => (color == Color::BLACK) == black_to_move,
+            None => false,
+        }
+    };
+    if engine_to_move(&chess_moves) && chess_moves.pop().is_some() {
+        taken_back += 1;
     }
 
-    server
+    let game = match replay_game(&game_record, &chess_moves) {
+        Ok(game) => game,
+        Err(e) => {
+            error!("Could not take back a move: {}", e);
+            return HttpResponse::InternalServerError().body(e);
+        }
+    };
+    for _ in 0..taken_back {
+        server.db.take_back_move(&game_id.to_string()).await;
+    }
+    server.db.open_voting(&game_id.to_string()).await;
+    // the time of the moves that were taken back is not given back, but the side to move gets
+    // the full time for its move again
+    if let Some(clock) = server.db.get_clock(&game_id.to_string()).await {
+        server.db.update_clock(&game_id.to_string(), &clock).await;
+    }
+    *server.game.lock().unwrap() = game;
+    info!("Took back {} moves", taken_back);
+
+    // if the engine plays white and its first move was taken back, it needs to move again
+    play_engine_move(&server).await;
+    HttpResponse::Ok().body("OK".to_string())
+}
+
+// returns the id of the stored move
+async fn play_move(server: &Server, chess_move: chess_move::Move) -> Result<i64, &'static str> {
+    // the game lock must not be held across the DB calls below, so we take everything we need
+    // out of the game first
+    let (game, captured_piece, new_game) = {
+        let mut game = server.game.lock().unwrap();
+        game.validate_move(chess_move)?;
+        let captured_piece = game.get_captured_piece(chess_move);
+        game.make_move(chess_move);
+        info!("Move {} is valid", &game.previous_move);
+
+        let played_game = game.clone();
+        if played_game.game_result.is_some() {
+            *game = server.variant.lock().unwrap().create_game();
+            (played_game, captured_piece, Some(game.clone()))
+        } else {
+            (played_game, captured_piece, None)
+        }
+    };
+
+    let move_id = server
         .db
-        .insert_move(
-            game.turn_number,
-            &game.id.to_string(),
-            &game.previous_move,
-            player_str,
-        )
+        .insert_move(&game, chess_move, captured_piece)
         .await;
     info!("Inserted move into DB");
+    server.db.open_voting(&game.id.to_string()).await;
+    if let Some(mut clock) = server.db.get_clock(&game.id.to_string()).await {
+        // the game is given after the move, so the player is the one that is not to move anymore
+        let player = match game.next_to_move {
+            Color::WHITE => Color::BLACK,
+            Color::BLACK => Color::WHITE,
+        };
+        clock.play_move(player);
+        server.db.update_clock(&game.id.to_string(), &clock).await;
+    }
 
     if let Some(result) = &game.game_result {
-        match result {
-            GameResult::BlackWon => {
-                info!("Black won, finishing game automatically...");
-                server.db.finish_game("0-1", &game.id.to_string()).await;
+        // the game ended by mate unless a win condition of the variant was reached or it was a
+        // stalemate
+        let termination = match (game.get_rules().get_winner(&game), result) {
+            (Some(_), _) => "VARIANT_WIN",
+            (None, GameResult::Draw) => "STALEMATE",
+            (None, _) => "CHECKMATE",
+        };
+        info!(
+            "Game over by {}, finishing game automatically with {}...",
+            termination,
+            result.as_str()
+        );
+        server
+            .db
+            .finish_game(result.as_str(), termination, &game.id.to_string())
+            .await;
+    }
+    if let Some(new_game) = new_game {
+        create_next_game(server, &new_game).await;
+    }
+
+    Ok(move_id)
+}
+
+async fn play_action(server: &Server, action: GameAction) -> Result<(), &'static str> {
+    let (game, new_game) = {
+        let mut game = server.game.lock().unwrap();
+        game.validate_and_make_action(action)?;
+        info!("Action {} is valid", action.as_str());
+
+        let played_game = game.clone();
+        if played_game.game_result.is_some() {
+            *game = server.variant.lock().unwrap().create_game();
+            (played_game, Some(game.clone()))
+        } else {
+            (played_game, None)
+        }
+    };
+    let game_id = game.id.to_string();
+
+    match &game.game_result {
+        // the side that offered a draw still has to move, so the voting for its move starts over
+        None => {
+            server.db.clear_votes().await;
+            server.db.open_voting(&game_id).await;
+        }
+        Some(result) => {
+            let termination = match action {
+                GameAction::Resign => "RESIGNATION",
+                _ => "AGREEMENT",
+            };
+            info!(
+                "Finishing game by {} with {}...",
+                termination,
+                result.as_str()
+            );
+            server
+                .db
+                .finish_game(result.as_str(), termination, &game_id)
+                .await;
+        }
+    }
+    if let Some(new_game) = new_game {
+        create_next_game(server, &new_game).await;
+    }
+
+    Ok(())
+}
+
+// ends the game if the side to move ran out of time and starts the next one, returns whether it
+// did. The engine gets to open the next game afterwards
+async fn check_clock(server: &Server) -> bool {
+    let (game_id, next_to_move) = {
+        let game = server.game.lock().unwrap();
+        (game.id.to_string(), game.next_to_move)
+    };
+    let Some(clock) = server.db.get_clock(&game_id).await else {
+        return false;
+    };
+    if !clock.is_flagged(next_to_move) {
+        return false;
+    }
+
+    let new_game = {
+        let mut game = server.game.lock().unwrap();
+        // a move could have been played in the meantime
+        if game.id.to_string() != game_id || game.next_to_move != next_to_move {
+            return false;
+        }
+        *game = server.variant.lock().unwrap().create_game();
+        game.clone()
+    };
+    let result = match next_to_move {
+        Color::WHITE => GameResult::BlackWon,
+        Color::BLACK => GameResult::WhiteWon,
+    };
+    info!(
+        "Out of time, finishing game automatically with {}...",
+        result.as_str()
+    );
+    server
+        .db
+        .finish_game(result.as_str(), "TIMEOUT", &game_id)
+        .await;
+    create_next_game(server, &new_game).await;
+
+    true
+}
+
+// new games that are started automatically keep the settings of the previous game
+async fn create_next_game(server: &Server, new_game: &Game) {
+    let opponent = *server.opponent.lock().unwrap();
+    let teams = server.teams.lock().unwrap().clone();
+    let time_control = *server.time_control.lock().unwrap();
+    let voting_method = *server.voting_method.lock().unwrap();
+    server
+        .db
+        .create_game(
+            new_game,
+            opponent.as_ref(),
+            teams.as_ref(),
+            time_control.as_ref(),
+            voting_method,
+            &server.voting_close_time,
+        )
+        .await;
+    info!("Created new game");
+}
+
+// lets the engine reply if the community plays against it and it is the engine's turn. Once a
+// game ends, the engine also opens the next one if it plays white
+async fn play_engine_move(server: &web::Data<Server>) {
+    loop {
+        let Some(opponent) = *server.opponent.lock().unwrap() else {
+            return;
+        };
+        let game = {
+            let game = server.game.lock().unwrap();
+            if opponent.color != game.next_to_move {
+                return;
             }
-            GameResult::WhiteWon => {
-                info!("White won, finishing game automatically...");
-                server.db.finish_game("1-0", &game.id.to_string()).await;
+            game.clone()
+        };
+        let draw_offered = game.draw_offer.is_some_and(|color| color != opponent.color);
+
+        info!("Searching engine move...");
+        let limits = opponent.strength.get_search_limits();
+        let result = match search_position(server, game, limits).await {
+            Ok(result) => result,
+            Err(e) => {
+                error!("Engine search failed: {}", e);
+                return;
+            }
+        };
+
+        // the engine accepts a draw offer of the community unless it thinks it is better
+        if draw_offered && result.score <= 0 {
+            info!(
+                "Engine accepts the draw offer with a score of {}",
+                result.score
+            );
+            if let Err(e) = play_action(server, GameAction::AcceptDraw).await {
+                error!("Engine could not accept the draw offer: {}", e);
+                return;
             }
+            continue;
+        }
+        let Some(engine_move) = result.best_move else {
+            return;
+        };
+        info!(
+            "Engine plays {} with a score of {} at depth {}",
+            engine_move, result.score, result.depth
+        );
+        if let Err(e) = play_move(server, engine_move).await {
+            error!("Engine move was not valid anymore: {}", e);
+            return;
         }
-        server.db.create_game(&game.id.to_string()).await;
-        *game = Game::new();
-        info!("Created new game");
     }
+}
 
-    HttpResponse::Ok().body("OK".to_string())
+// keeps the engine's choice in the position of the community's move with it, to see how often they
+// agree. The randomness is left out, so it is always the move the engine thinks is best
+async fn record_engine_move(server: &web::Data<Server>, game: Game, move_id: i64) {
+    let limits = SearchLimits {
+        randomness: 0,
+        ..ANALYSIS_STRENGTH.get_search_limits()
+    };
+    match search_position(server, game, limits).await {
+        Ok(SearchResult {
+            best_move: Some(engine_move),
+            ..
+        }) => server.db.set_engine_move(move_id, engine_move).await,
+        Ok(_) => (),
+        Err(e) => error!("Could not search the engine's move: {}", e),
+    }
+}
+
+// searches with the UCI engine if one is configured and with the built-in engine otherwise. Both
+// block for a while, so they run on the blocking thread pool
+async fn search_position(
+    server: &web::Data<Server>,
+    mut game: Game,
+    limits: SearchLimits,
+) -> Result<SearchResult, &'static str> {
+    let server = server.clone();
+    // variants with rules of their own are left to the built-in engine
+    web::block(move || match &server.uci_engine {
+        Some(uci_engine) if game.variant.has_standard_rules() => {
+            uci_engine.lock().unwrap().search(&game, limits)
+        }
+        _ => Ok(search(&mut game, limits)),
+    })
+    .await
+    .map_err(|_| ENGINE_SEARCH_ERROR)?
+}
+
+// voters are told apart by their address, which is only kept hashed
+fn get_request_voter_hash(server: &Server, http_req: &HttpRequest) -> String {
+    let address = get_voter_address(http_req, server.trust_proxy_headers);
+    get_voter_hash(&server.voter_salt, &address)
+}
+
+// replays the moves by the rules of the variant from the position the game started from, which
+// is not the standard one in Chess960 or Horde
+fn replay_game(
+    game_record: &GameRecord,
+    chess_moves: &[chess_move::Move],
+) -> Result<Game, &'static str> {
+    let variant = Variant::from_name(&game_record.variant).ok_or(INVALID_HISTORY_ERROR)?;
+    let mut game = game_from_variant_fen(&game_record.starting_fen, variant)?;
+    game.id = Uuid::parse_str(&game_record.game_id).map_err(|_| INVALID_HISTORY_ERROR)?;
+    replay_moves(&mut game, chess_moves)?;
+
+    Ok(game)
+}
+
+// the openings are only known for the standard start position
+fn get_opening(variant: Variant, moves: &[Move]) -> Option<Opening> {
+    if variant != Variant::Standard {
+        return None;
+    }
+    get_chess_moves(moves)
+        .ok()
+        .and_then(|chess_moves| classify_opening(&chess_moves))
+}
+
+fn get_chess_moves(moves: &[Move]) -> Result<Vec<chess_move::Move>, &'static str> {
+    moves
+        .iter()
+        .map(|played_move| played_move.get_chess_move())
+        .collect()
+}
+
+// a time of day like 00:00 or 18:30
+fn is_valid_time(time: &str) -> bool {
+    let Some((hours, minutes)) = time.split_once(':') else {
+        return false;
+    };
+    let is_two_digits = |part: &str| part.len() == 2 && part.chars().all(|ch| ch.is_ascii_digit());
+
+    is_two_digits(hours) && is_two_digits(minutes) && hours < "24" && minutes < "60"
+}
+
+#[cfg(test)]
+mod test_voting_close_time {
+    use crate::is_valid_time;
+
+    #[test]
+    fn test_valid_time() {
+        assert!(is_valid_time("00:00"));
+        assert!(is_valid_time("23:59"));
+        assert!(!is_valid_time("24:00"));
+        assert!(!is_valid_time("12:60"));
+        assert!(!is_valid_time("9:30"));
+        assert!(!is_valid_time("midnight"));
+    }
 }
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{game::chess_piece::Color, utils::error::INVALID_TIME_CONTROL_ERROR};
+
+/// How long each side may take for its moves, in seconds since correspondence games are counted
+/// in days, e.g. 86400 for a day per move.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TimeControl {
+    /// the most time a single move may take
+    #[serde(default)]
+    pub seconds_per_move: Option<u64>,
+    /// the budget of each side for the whole game
+    #[serde(default)]
+    pub total_seconds: Option<u64>,
+    /// added to the budget of a side after each of its moves, like a Fischer clock
+    #[serde(default)]
+    pub increment_seconds: u64,
+}
+
+impl TimeControl {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match (self.seconds_per_move, self.total_seconds) {
+            (None, None) | (Some(0), _) | (_, Some(0)) => Err(INVALID_TIME_CONTROL_ERROR),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The clock of a game with a time control, as it is stored in the Game table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clock {
+    pub time_control: TimeControl,
+    /// the budgets at the start of the current move, None without a total time
+    pub white_time_left: Option<u64>,
+    pub black_time_left: Option<u64>,
+    /// how long the side to move has been thinking
+    pub elapsed: u64,
+}
+
+impl Clock {
+    pub fn new(time_control: TimeControl) -> Clock {
+        Clock {
+            time_control,
+            white_time_left: time_control.total_seconds,
+            black_time_left: time_control.total_seconds,
+            elapsed: 0,
+        }
+    }
+    fn get_budget(&self, color: Color) -> Option<u64> {
+        match color {
+            Color::WHITE => self.white_time_left,
+            Color::BLACK => self.black_time_left,
+        }
+    }
+    /// The budget of the side as it is right now, with the time of the running move taken off.
+    pub fn get_time_left(&self, color: Color, next_to_move: Color) -> Option<u64> {
+        let budget = self.get_budget(color)?;
+        if color == next_to_move {
+            Some(budget.saturating_sub(self.elapsed))
+        } else {
+            Some(budget)
+        }
+    }
+    /// The seconds until the side to move loses on time, whichever of its limits comes first.
+    pub fn get_move_time_left(&self, next_to_move: Color) -> u64 {
+        let move_time_left = self
+            .time_control
+            .seconds_per_move
+            .map(|seconds| seconds.saturating_sub(self.elapsed));
+
+        match (
+            move_time_left,
+            self.get_time_left(next_to_move, next_to_move),
+        ) {
+            (Some(move_time_left), Some(time_left)) => move_time_left.min(time_left),
+            (Some(time_left), None) | (None, Some(time_left)) => time_left,
+            (None, None) => u64::MAX,
+        }
+    }
+    pub fn is_flagged(&self, next_to_move: Color) -> bool {
+        self.get_move_time_left(next_to_move) == 0
+    }
+    /// Stops the clock of the side that moved, adds its increment and starts the next move.
+    pub fn play_move(&mut self, player: Color) {
+        let budget = self
+            .get_time_left(player, player)
+            .map(|time_left| time_left + self.time_control.increment_seconds);
+        match player {
+            Color::WHITE => self.white_time_left = budget,
+            Color::BLACK => self.black_time_left = budget,
+        }
+        self.elapsed = 0;
+    }
+}
+
+#[cfg(test)]
+mod test_time_control {
+    use crate::{
+        game::chess_piece::Color,
+        time_control::{Clock, TimeControl},
+    };
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn test_seconds_per_move() {
+        let mut clock = Clock::new(TimeControl {
+            seconds_per_move: Some(3 * DAY),
+            total_seconds: None,
+            increment_seconds: 0,
+        });
+        clock.elapsed = 2 * DAY;
+        assert_eq!(clock.get_move_time_left(Color::WHITE), DAY);
+        assert_eq!(clock.get_time_left(Color::WHITE, Color::WHITE), None);
+        assert!(!clock.is_flagged(Color::WHITE));
+
+        // every move gets the full time again
+        clock.play_move(Color::WHITE);
+        assert_eq!(clock.get_move_time_left(Color::BLACK), 3 * DAY);
+        clock.elapsed = 3 * DAY;
+        assert!(clock.is_flagged(Color::BLACK));
+    }
+
+    #[test]
+    fn test_total_time_with_increment() {
+        let mut clock = Clock::new(TimeControl {
+            seconds_per_move: None,
+            total_seconds: Some(10 * DAY),
+            increment_seconds: DAY,
+        });
+        clock.elapsed = 4 * DAY;
+        assert_eq!(
+            clock.get_time_left(Color::WHITE, Color::WHITE),
+            Some(6 * DAY)
+        );
+        assert_eq!(
+            clock.get_time_left(Color::BLACK, Color::WHITE),
+            Some(10 * DAY)
+        );
+        clock.play_move(Color::WHITE);
+        assert_eq!(clock.white_time_left, Some(7 * DAY));
+
+        clock.elapsed = 11 * DAY;
+        assert_eq!(clock.get_move_time_left(Color::BLACK), 0);
+        assert!(clock.is_flagged(Color::BLACK));
+    }
+
+    #[test]
+    fn test_both_limits() {
+        // the move limit runs out first although the budget would last longer
+        let mut clock = Clock::new(TimeControl {
+            seconds_per_move: Some(2 * DAY),
+            total_seconds: Some(5 * DAY),
+            increment_seconds: 0,
+        });
+        clock.elapsed = DAY;
+        assert_eq!(clock.get_move_time_left(Color::WHITE), DAY);
+
+        // and the budget once most of it is used up
+        clock.white_time_left = Some(DAY / 2);
+        assert_eq!(clock.get_move_time_left(Color::WHITE), 0);
+    }
+
+    #[test]
+    fn test_validate() {
+        let time_control = TimeControl {
+            seconds_per_move: None,
+            total_seconds: None,
+            increment_seconds: DAY,
+        };
+        assert!(time_control.validate().is_err());
+        assert!(TimeControl {
+            seconds_per_move: Some(0),
+            ..time_control
+        }
+        .validate()
+        .is_err());
+        assert!(TimeControl {
+            seconds_per_move: Some(DAY),
+            ..time_control
+        }
+        .validate()
+        .is_ok());
+    }
+}
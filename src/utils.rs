@@ -2,7 +2,8 @@ pub mod convert_notation;
 pub mod error;
 pub mod request;
 pub mod response;
+pub mod voter;
 
 pub fn is_in_bounds(row: i32, col: i32) -> bool {
-    row >= 0 && row <= 7 && col >= 0 && col <= 7
+    (0..=7).contains(&row) && (0..=7).contains(&col)
 }
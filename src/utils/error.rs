@@ -1,11 +1,39 @@
-pub const GENERAL_ERROR: &'static str = "Invalid move";
-pub const SQUARE_OUT_OF_BOUNDS_ERROR: &'static str =
+pub const GENERAL_ERROR: &str = "Invalid move";
+pub const SQUARE_OUT_OF_BOUNDS_ERROR: &str =
     "The selected square is not inside the bounds of the chessboard";
-pub const PIECE_IN_THE_WAY_ERROR: &'static str = "There is a piece in the way of your move";
-pub const CAPTURE_OWN_PIECE_ERROR: &'static str = "You cannot capture your own piece";
-pub const PROMOTION_ERROR: &'static str = "No promotion piece specified";
-pub const NO_PIECE_SELECTED_ERROR: &'static str = "You have not selected any piece";
-pub const INVALID_FROM_FIELD: &'static str = "The from field in your requests body is incorrect";
-pub const INVALID_TO_FIELD: &'static str = "The to field in your requests body is incorrect";
-pub const INVALID_CASTLE_ERROR: &'static str = "That castle move is invalid";
-pub const CHECK_ERROR: &'static str = "Your king is in check";
+pub const PIECE_IN_THE_WAY_ERROR: &str = "There is a piece in the way of your move";
+pub const CAPTURE_OWN_PIECE_ERROR: &str = "You cannot capture your own piece";
+pub const PROMOTION_ERROR: &str = "No promotion piece specified";
+pub const NO_PIECE_SELECTED_ERROR: &str = "You have not selected any piece";
+pub const OPPONENT_PIECE_SELECTED_ERROR: &str = "You cannot move a piece of your opponent";
+pub const INVALID_SQUARE_ERROR: &str = "A square needs to be given as file and rank, e.g. e4";
+pub const INVALID_UCI_MOVE_ERROR: &str =
+    "A move needs to be given as from and to square, e.g. e2e4 or e7e8q";
+pub const INVALID_CASTLE_ERROR: &str = "That castle move is invalid";
+pub const CHECK_ERROR: &str = "Your king is in check";
+pub const INVALID_FEN_ERROR: &str = "The given FEN does not describe a valid position";
+pub const UCI_ENGINE_START_ERROR: &str = "The UCI engine could not be started";
+pub const UCI_ENGINE_ERROR: &str = "The UCI engine stopped responding";
+pub const ENGINE_SEARCH_ERROR: &str = "The engine could not search the position";
+pub const INVALID_HISTORY_ERROR: &str = "The stored moves do not form a legal game";
+pub const NO_MOVE_TO_TAKE_BACK_ERROR: &str = "There is no move to take back in the current game";
+pub const PLY_OUT_OF_RANGE_ERROR: &str = "The game has not reached the requested ply";
+pub const ALREADY_VOTED_ERROR: &str = "You have already voted for this move";
+pub const VOTING_CLOSED_ERROR: &str = "The voting for this move has already closed";
+pub const EMPTY_BALLOT_ERROR: &str = "A ballot needs at least one move";
+pub const GAME_FINISHED_ERROR: &str = "The game is already finished";
+pub const DRAW_ALREADY_OFFERED_ERROR: &str = "A draw has already been offered";
+pub const NO_DRAW_OFFER_ERROR: &str = "There is no draw offer of the opponent to accept";
+pub const SAME_TEAM_NAMES_ERROR: &str = "The two teams need different names";
+pub const TEAMS_WITH_ENGINE_ERROR: &str =
+    "A game is either played by two teams or against the engine";
+pub const NO_TEAMS_ERROR: &str = "The current game is not played by teams";
+pub const UNKNOWN_TEAM_ERROR: &str = "That team does not play in the current game";
+pub const OTHER_TEAM_ERROR: &str = "You have already joined the other team";
+pub const NOT_TEAMS_TURN_ERROR: &str = "Only the team to move can vote, join it first";
+pub const INVALID_TIME_CONTROL_ERROR: &str =
+    "A time control needs a time per move or a total time that is not 0";
+pub const OUT_OF_TIME_ERROR: &str = "The side to move ran out of time, the game is over";
+pub const INVALID_CHESS960_POSITION_ERROR: &str = "Chess960 positions are numbered from 0 to 959";
+pub const KING_CAPTURE_ERROR: &str = "The king cannot capture in Atomic, it would blow itself up";
+pub const EXPLODE_OWN_KING_ERROR: &str = "The capture would blow up your own king";
@@ -1,14 +1,144 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    engine::EngineOpponent,
+    game::{
+        action::GameAction,
+        chess_move::{Move, Square},
+        chess_piece::Piece,
+        variant::Variant,
+    },
+    time_control::TimeControl,
+    utils::convert_notation::get_promotion_piece,
+    voting::{Teams, VotingMethod},
+};
 
 #[derive(Deserialize, Debug)]
 pub struct MoveRequest {
-    pub from: String,
-    pub to: String,
-    pub promotion: char,
+    pub from: Square,
+    pub to: Square,
+    // sent as a single char like "Q", anything else (usually " ") means no promotion
+    #[serde(default, deserialize_with = "deserialize_promotion")]
+    pub promotion: Option<Piece>,
+}
+
+impl MoveRequest {
+    pub fn get_move(&self) -> Move {
+        Move::new(self.from, self.to, self.promotion)
+    }
+}
+
+// a move or an action like {"action": "RESIGN"}
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum BallotEntry {
+    ACTION { action: GameAction },
+    MOVE(MoveRequest),
+}
+
+// a single entry or a ballot of entries in the order the voter prefers them
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum VoteRequest {
+    BALLOT { moves: Vec<BallotEntry> },
+    ENTRY(BallotEntry),
+}
+
+impl VoteRequest {
+    pub fn get_entries(&self) -> Vec<&BallotEntry> {
+        match self {
+            VoteRequest::BALLOT { moves } => moves.iter().collect(),
+            VoteRequest::ENTRY(entry) => vec![entry],
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ActionRequest {
+    pub action: GameAction,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JoinTeamRequest {
+    // the name of one of the teams of the current game
+    pub team: String,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct FinishRequest {
     pub game_id: String,
     pub game_result: String,
+    // who the community plays against in the next game, by default the community plays both sides
+    #[serde(default)]
+    pub opponent: Option<EngineOpponent>,
+    // the two teams that play the next game instead of a single community, can't be combined with
+    // an opponent
+    #[serde(default)]
+    pub teams: Option<Teams>,
+    // the next game is played without a time control if it is left out
+    #[serde(default)]
+    pub time_control: Option<TimeControl>,
+    // how the moves of the next game are voted for, plurality by default
+    #[serde(default)]
+    pub voting_method: VotingMethod,
+    // the variant of the next game, standard chess by default
+    #[serde(default)]
+    pub variant: Variant,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PositionQuery {
+    // the number of half moves played, 0 is the starting position
+    pub ply: usize,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub enum TurnoutInterval {
+    #[default]
+    DAY,
+    HOUR,
+}
+
+impl TurnoutInterval {
+    pub fn get_period_format(&self) -> &'static str {
+        match self {
+            TurnoutInterval::DAY => "%Y-%m-%d",
+            TurnoutInterval::HOUR => "%Y-%m-%d %H:00",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TurnoutQuery {
+    #[serde(default)]
+    pub interval: TurnoutInterval,
+}
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Deserialize, Debug)]
+pub struct PageQuery {
+    // starts at 1
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl PageQuery {
+    pub fn get_page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+    pub fn get_page_size(&self) -> u32 {
+        self.page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+fn deserialize_promotion<'de, D>(deserializer: D) -> Result<Option<Piece>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let promotion_ch = char::deserialize(deserializer)?;
+    Ok(get_promotion_piece(promotion_ch.to_ascii_uppercase()))
 }
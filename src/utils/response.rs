@@ -1,12 +1,110 @@
-use crate::game::chess_piece::{ChessPiece, Color, Piece};
+use serde::Serialize;
 
-pub fn serialize_field(field: &Vec<Vec<Option<ChessPiece>>>) -> Vec<Vec<String>> {
-    let mut serialized_fields: Vec<Vec<String>> =
-        vec![vec!["".to_string(); field.len()]; field[0].len()];
+use crate::{
+    db::{GameRecord, GameSummary, Move},
+    engine::SearchResult,
+    game::{
+        chess_piece::{Color, Piece},
+        opening::Opening,
+        ChecksGiven, Field,
+    },
+    voting::{Tally, VotingMethod},
+};
 
-    for i in 0..field.len() {
-        for j in 0..field[0].len() {
-            match field[i][j] {
+#[derive(Serialize, Debug)]
+pub struct GameStatusResponse {
+    // None until the moves match a known opening
+    pub opening: Option<Opening>,
+    // when the voting for the next move closes, in UTC like 2024-05-01 00:00:00
+    pub closes_at: Option<String>,
+    // the side whose draw offer can be accepted by voting for ACCEPT_DRAW
+    pub draw_offer: Option<Color>,
+    // None without a time control
+    pub clock: Option<ClockResponse>,
+    // the checks both sides have given, only in Three-check
+    pub checks_given: Option<ChecksGiven>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ClockResponse {
+    // in seconds, None without a total time
+    pub white_time_left: Option<u64>,
+    pub black_time_left: Option<u64>,
+    // the seconds until the side to move loses on time
+    pub move_time_left: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GameListResponse {
+    pub games: Vec<GameSummary>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GameDetailResponse {
+    pub game: GameRecord,
+    pub moves: Vec<Move>,
+    // the position after the last move
+    pub field: Vec<Vec<String>>,
+    pub fen: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PositionResponse {
+    pub ply: usize,
+    pub field: Vec<Vec<String>>,
+    pub fen: String,
+    // the move that led to the position, None for the starting position
+    pub last_move: Option<Move>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TeamResponse {
+    pub color: Color,
+    pub name: String,
+    pub members: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TallyResponse {
+    pub voting_method: VotingMethod,
+    #[serde(flatten)]
+    pub tally: Tally,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SearchResponse {
+    // in UCI notation, e.g. e2e4
+    pub best_move: Option<String>,
+    // in centipawns from the point of view of the side to move
+    pub score: i32,
+    pub depth: u32,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CandidateAnalysis {
+    pub move_notation: String,
+    pub votes: u32,
+    // None if the voted move is not legal in the current position
+    pub score: Option<i32>,
+    // how many centipawns worse than the best move the candidate is
+    pub score_loss: Option<i32>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AnalysisResponse {
+    pub position: SearchResponse,
+    pub candidates: Vec<CandidateAnalysis>,
+}
+
+pub fn serialize_field(field: &Field) -> Vec<Vec<String>> {
+    let mut serialized_fields: Vec<Vec<String>> = vec![vec!["".to_string(); 8]; 8];
+
+    for (i, row) in field.iter().enumerate() {
+        for (j, square) in row.iter().enumerate() {
+            match square {
                 None => (),
                 Some(chess_piece) => {
                     match chess_piece.color {
@@ -28,3 +126,11 @@ pub fn serialize_field(field: &Vec<Vec<Option<ChessPiece>>>) -> Vec<Vec<String>>
 
     serialized_fields
 }
+
+pub fn serialize_search_result(result: &SearchResult) -> SearchResponse {
+    SearchResponse {
+        best_move: result.best_move.map(|best_move| best_move.to_uci()),
+        score: result.score,
+        depth: result.depth,
+    }
+}
@@ -0,0 +1,95 @@
+use actix_web::HttpRequest;
+use sha2::{Digest, Sha256};
+
+/// The address a request is counted for. The `Forwarded` and `X-Forwarded-For` headers are set by
+/// the client, so they are only trusted if the server is told that it runs behind a proxy which
+/// sets them. Otherwise anyone could vote again, or for the other team, with a made up address.
+pub fn get_voter_address(http_req: &HttpRequest, trust_proxy_headers: bool) -> String {
+    match trust_proxy_headers {
+        true => http_req
+            .connection_info()
+            .realip_remote_addr()
+            .unwrap_or_default()
+            .to_string(),
+        false => http_req
+            .peer_addr()
+            .map(|address| address.ip().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Identifies a voter in the vote log without storing their address. The salt keeps the hash from
+/// being reversed by hashing every possible address.
+pub fn get_voter_hash(salt: &str, address: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(address.as_bytes());
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_voter {
+    use actix_web::test::TestRequest;
+
+    use crate::{
+        game::chess_piece::Color,
+        utils::voter::{get_voter_address, get_voter_hash},
+        voting::Teams,
+    };
+
+    #[test]
+    fn test_voter_hash() {
+        let hash = get_voter_hash("salt", "127.0.0.1");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, get_voter_hash("salt", "127.0.0.1"));
+        assert_ne!(hash, get_voter_hash("salt", "127.0.0.2"));
+        assert_ne!(hash, get_voter_hash("other salt", "127.0.0.1"));
+    }
+
+    #[test]
+    fn test_spoofed_address() {
+        let request = TestRequest::default()
+            .peer_addr("10.0.0.1:50000".parse().unwrap())
+            .to_http_request();
+        let spoofed_request = TestRequest::default()
+            .peer_addr("10.0.0.1:50001".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "10.0.0.2"))
+            .to_http_request();
+
+        // a made up address doesn't make the voter a new one
+        let address = get_voter_address(&request, false);
+        assert_eq!(address, "10.0.0.1");
+        assert_eq!(get_voter_address(&spoofed_request, false), address);
+
+        // behind a proxy the header is what tells the voters apart
+        assert_eq!(get_voter_address(&spoofed_request, true), "10.0.0.2");
+    }
+
+    #[test]
+    fn test_spoofed_address_cannot_vote_for_other_team() {
+        let teams = Teams {
+            white: "Reddit".to_string(),
+            black: "Twitter".to_string(),
+        };
+        let spoofed_request = TestRequest::default()
+            .peer_addr("10.0.0.1:50000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "10.0.0.2"))
+            .to_http_request();
+
+        // the voter at 10.0.0.1 joined white, so a made up address doesn't get them into black
+        let memberships = [(get_voter_hash("salt", "10.0.0.1"), teams.white.clone())];
+        let voter_hash = get_voter_hash("salt", &get_voter_address(&spoofed_request, false));
+        let voter_teams: Vec<String> = memberships
+            .into_iter()
+            .filter(|(member_hash, _)| *member_hash == voter_hash)
+            .map(|(_, team)| team)
+            .collect();
+        assert_eq!(voter_teams, vec![teams.white.clone()]);
+        assert!(!teams.can_vote(&voter_teams, Color::BLACK));
+    }
+}
@@ -0,0 +1,303 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{db::Vote, game::chess_piece::Color, utils::error::SAME_TEAM_NAMES_ERROR};
+
+/// How the ballots of a turn decide the move that is played. A ballot is a list of moves in the
+/// order the voter prefers them.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub enum VotingMethod {
+    /// only the first move of every ballot counts
+    #[default]
+    PLURALITY,
+    /// ranked choice counted as an instant runoff: the move with the fewest first choices is
+    /// eliminated until one move has the majority, ballots whose first choice is eliminated count
+    /// for their next choice
+    RANKED,
+    /// every move on a ballot counts once, the order does not matter
+    APPROVAL,
+}
+
+impl VotingMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VotingMethod::PLURALITY => "PLURALITY",
+            VotingMethod::RANKED => "RANKED",
+            VotingMethod::APPROVAL => "APPROVAL",
+        }
+    }
+    /// The moves of a ballot that are added to the running tally of `/game/current_votes`.
+    pub fn get_counted_moves<'a>(&self, ballot: &'a [String]) -> &'a [String] {
+        match self {
+            VotingMethod::PLURALITY | VotingMethod::RANKED => &ballot[..ballot.len().min(1)],
+            VotingMethod::APPROVAL => ballot,
+        }
+    }
+}
+
+/// Two communities that play each other, e.g. Twitter with white and Reddit with black. Voters
+/// join a team by its name, so they stay in their team for later games of the same match even if
+/// the colors are switched.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Teams {
+    pub white: String,
+    pub black: String,
+}
+
+impl Teams {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.white == self.black {
+            return Err(SAME_TEAM_NAMES_ERROR);
+        }
+
+        Ok(())
+    }
+    pub fn get_name(&self, color: Color) -> &str {
+        match color {
+            Color::WHITE => &self.white,
+            Color::BLACK => &self.black,
+        }
+    }
+    pub fn get_color(&self, name: &str) -> Option<Color> {
+        [Color::WHITE, Color::BLACK]
+            .into_iter()
+            .find(|&color| self.get_name(color) == name)
+    }
+    /// Whether a voter in the given teams may vote for the side to move. Someone who joined both
+    /// teams in earlier matches can't vote in a game between them.
+    pub fn can_vote(&self, voter_teams: &[String], next_to_move: Color) -> bool {
+        let is_member = |color| voter_teams.iter().any(|team| team == self.get_name(color));
+        let opponent = match next_to_move {
+            Color::WHITE => Color::BLACK,
+            Color::BLACK => Color::WHITE,
+        };
+        is_member(next_to_move) && !is_member(opponent)
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Tally {
+    pub winner: Option<String>,
+    // the votes per move in every round, sorted by votes. Only ranked choice has more than one
+    pub rounds: Vec<Vec<Vote>>,
+}
+
+/// Counts the ballots with the given method. Ties go to the move that was voted for first.
+pub fn tally(method: VotingMethod, ballots: &[Vec<String>]) -> Tally {
+    match method {
+        VotingMethod::PLURALITY => tally_plurality(ballots),
+        VotingMethod::RANKED => tally_instant_runoff(ballots),
+        VotingMethod::APPROVAL => tally_approval(ballots),
+    }
+}
+
+fn tally_plurality(ballots: &[Vec<String>]) -> Tally {
+    let first_choices: Vec<&String> = ballots.iter().filter_map(|ballot| ballot.first()).collect();
+    let round = count_votes(&first_choices);
+
+    Tally {
+        winner: round.first().map(|vote| vote.move_notation.clone()),
+        rounds: vec![round],
+    }
+}
+
+fn tally_approval(ballots: &[Vec<String>]) -> Tally {
+    let approvals: Vec<&String> = ballots.iter().flatten().collect();
+    let round = count_votes(&approvals);
+
+    Tally {
+        winner: round.first().map(|vote| vote.move_notation.clone()),
+        rounds: vec![round],
+    }
+}
+
+fn tally_instant_runoff(ballots: &[Vec<String>]) -> Tally {
+    let mut eliminated: Vec<&String> = vec![];
+    let mut rounds = vec![];
+
+    loop {
+        // every ballot counts for its best move that is still in the race, ballots without one
+        // are exhausted and left out of the majority
+        let choices: Vec<&String> = ballots
+            .iter()
+            .filter_map(|ballot| {
+                ballot
+                    .iter()
+                    .find(|notation| !eliminated.contains(notation))
+            })
+            .collect();
+        let round = count_votes(&choices);
+
+        let winner = match round.first() {
+            None => None,
+            Some(leader) if 2 * leader.votes > choices.len() as u32 || round.len() == 1 => {
+                Some(leader.move_notation.clone())
+            }
+            Some(_) => {
+                // the last move of the round has the fewest votes and was voted for last
+                let last = &round[round.len() - 1].move_notation;
+                eliminated.push(
+                    choices
+                        .iter()
+                        .copied()
+                        .find(|&choice| choice == last)
+                        .unwrap(),
+                );
+                rounds.push(round);
+                continue;
+            }
+        };
+        rounds.push(round);
+
+        return Tally { winner, rounds };
+    }
+}
+
+// sorted by votes, moves with the same votes keep the order they were first voted for in
+fn count_votes(notations: &[&String]) -> Vec<Vote> {
+    let mut first_seen: HashMap<&String, usize> = HashMap::new();
+    let mut votes: Vec<Vote> = vec![];
+    for &notation in notations {
+        let index = *first_seen.entry(notation).or_insert_with(|| {
+            votes.push(Vote {
+                move_notation: notation.clone(),
+                votes: 0,
+            });
+            votes.len() - 1
+        });
+        votes[index].votes += 1;
+    }
+    // the sort is stable, so ties stay in the order of the first vote
+    votes.sort_by_key(|vote| Reverse(vote.votes));
+
+    votes
+}
+
+#[cfg(test)]
+mod test_voting {
+    use crate::{
+        game::chess_piece::Color,
+        voting::{tally, Teams, VotingMethod},
+    };
+
+    fn ballots(ballots: &[&str]) -> Vec<Vec<String>> {
+        ballots
+            .iter()
+            .map(|ballot| ballot.split_whitespace().map(String::from).collect())
+            .collect()
+    }
+
+    fn get_round(
+        method: VotingMethod,
+        ballots: &[Vec<String>],
+        round: usize,
+    ) -> Vec<(String, u32)> {
+        tally(method, ballots).rounds[round]
+            .iter()
+            .map(|vote| (vote.move_notation.clone(), vote.votes))
+            .collect()
+    }
+
+    #[test]
+    fn test_plurality() {
+        let ballots = ballots(&["e4 d4", "d4", "d4 e4", "e4", "c4 e4"]);
+        let result = tally(VotingMethod::PLURALITY, &ballots);
+        // e4 and d4 are tied, e4 was voted for first
+        assert_eq!(result.winner.as_deref(), Some("e4"));
+        assert_eq!(result.rounds.len(), 1);
+        assert_eq!(
+            get_round(VotingMethod::PLURALITY, &ballots, 0),
+            [
+                ("e4".to_string(), 2),
+                ("d4".to_string(), 2),
+                ("c4".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instant_runoff() {
+        // d4 has the most first choices, but the voters of c4 prefer e4 over d4
+        let ballots = ballots(&["d4", "d4 c4", "d4", "e4", "e4 d4", "c4 e4", "c4 e4 d4"]);
+        let result = tally(VotingMethod::RANKED, &ballots);
+        assert_eq!(result.winner.as_deref(), Some("e4"));
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(
+            get_round(VotingMethod::RANKED, &ballots, 1),
+            [("e4".to_string(), 4), ("d4".to_string(), 3)]
+        );
+        assert_eq!(
+            tally(VotingMethod::PLURALITY, &ballots).winner.as_deref(),
+            Some("d4")
+        );
+    }
+
+    #[test]
+    fn test_instant_runoff_with_exhausted_ballots() {
+        // the c4 ballot has no other choice, so 2 of the 3 remaining ballots are a majority
+        let ballots = ballots(&["e4", "e4", "d4", "c4"]);
+        let result = tally(VotingMethod::RANKED, &ballots);
+        assert_eq!(result.winner.as_deref(), Some("e4"));
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(
+            get_round(VotingMethod::RANKED, &ballots, 1),
+            [("e4".to_string(), 2), ("d4".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_approval() {
+        // everyone can live with Nf3, while the first choices are split
+        let ballots = ballots(&["e4 Nf3", "d4 Nf3", "Nf3", "e4"]);
+        let result = tally(VotingMethod::APPROVAL, &ballots);
+        assert_eq!(result.winner.as_deref(), Some("Nf3"));
+        assert_eq!(
+            get_round(VotingMethod::APPROVAL, &ballots, 0),
+            [
+                ("Nf3".to_string(), 3),
+                ("e4".to_string(), 2),
+                ("d4".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_ballots() {
+        for method in [
+            VotingMethod::PLURALITY,
+            VotingMethod::RANKED,
+            VotingMethod::APPROVAL,
+        ] {
+            let result = tally(method, &[]);
+            assert_eq!(result.winner, None);
+            assert_eq!(result.rounds.len(), 1);
+            assert!(result.rounds[0].is_empty());
+        }
+    }
+
+    #[test]
+    fn test_teams() {
+        let teams = Teams {
+            white: "Twitter".to_string(),
+            black: "Reddit".to_string(),
+        };
+        assert!(teams.validate().is_ok());
+        assert_eq!(teams.get_color("Reddit"), Some(Color::BLACK));
+        assert_eq!(teams.get_color("Discord"), None);
+
+        let twitter = ["Twitter".to_string()];
+        assert!(teams.can_vote(&twitter, Color::WHITE));
+        assert!(!teams.can_vote(&twitter, Color::BLACK));
+        assert!(!teams.can_vote(&[], Color::WHITE));
+        // a voter in both teams could decide the moves of both sides
+        let both = ["Reddit".to_string(), "Twitter".to_string()];
+        assert!(!teams.can_vote(&both, Color::WHITE));
+
+        let same_names = Teams {
+            white: "Twitter".to_string(),
+            black: "Twitter".to_string(),
+        };
+        assert!(same_names.validate().is_err());
+    }
+}